@@ -0,0 +1,62 @@
+//! Benchmarks the parse step and both parts of every registered day on its real input.
+//!
+//! Inputs are read once from `AOC_INPUTS_DIR`, `input/` by default; days without one are
+//! skipped. To compare a branch against `main`:
+//!
+//! ```text
+//! git checkout main && cargo bench -- --save-baseline main
+//! git checkout my-branch && cargo bench -- --baseline main
+//! ```
+//!
+//! Results and baselines are kept in `target/criterion`, a single day runs with e.g.
+//! `cargo bench -- day6/`.
+
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::selection::Part;
+use advent_of_code_2022::SOLUTIONS;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn inputs_dir() -> PathBuf {
+    std::env::var_os("AOC_INPUTS_DIR").map_or_else(
+        || Path::new(env!("CARGO_MANIFEST_DIR")).join("input"),
+        PathBuf::from,
+    )
+}
+
+fn solutions(c: &mut Criterion) {
+    let inputs_dir = inputs_dir();
+    for solution in SOLUTIONS {
+        let day = solution.day();
+        let input = match InputSource::for_day(&inputs_dir, day).load_for_day(day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping {}", e);
+                continue;
+            }
+        };
+        let parsed = match solution.parse_input(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("skipping {}", e);
+                continue;
+            }
+        };
+
+        let mut group = c.benchmark_group(format!("day{}", day));
+        group.bench_function("parse", |b| {
+            b.iter(|| solution.parse_input(black_box(&input)))
+        });
+        for part in [Part::One, Part::Two] {
+            group.bench_function(format!("part {}", part), |b| {
+                b.iter(|| solution.solve(black_box(parsed.as_ref()), part))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
@@ -0,0 +1,223 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Result of a single puzzle part.
+///
+/// Numbers compare by value regardless of signedness, and a text compares equal to a block with
+/// the same content, so answers read back from a file match the ones computed by a solution.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+    /// Multi-line text, e.g. letters drawn as ASCII art.
+    Block(String),
+}
+
+impl Answer {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Signed(_) => "signed",
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Text(_) => "text",
+            Answer::Block(_) => "block",
+        }
+    }
+
+    pub fn is_block(&self) -> bool {
+        matches!(self, Answer::Block(_))
+    }
+
+    fn key(&self) -> Key<'_> {
+        match self {
+            Answer::Signed(n) => Key::Number(*n as i128),
+            Answer::Unsigned(n) => Key::Number(*n as i128),
+            Answer::Text(s) | Answer::Block(s) => Key::Text(s),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum Key<'a> {
+    Number(i128),
+    Text(&'a str),
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Answer {}
+
+impl std::hash::Hash for Answer {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Text(s) | Answer::Block(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(n) = str::parse::<u64>(s) {
+            return Ok(Answer::Unsigned(n));
+        }
+        if let Ok(n) = str::parse::<i64>(s) {
+            return Ok(Answer::Signed(n));
+        }
+        Ok(Answer::from(s))
+    }
+}
+
+macro_rules! from_number {
+    ($variant:ident, $target:ty, $($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(n: $source) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+
+            impl PartialEq<$source> for Answer {
+                fn eq(&self, other: &$source) -> bool {
+                    self.key() == Key::Number(*other as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_number!(Signed, i64, i8, i16, i32, i64, isize);
+from_number!(Unsigned, u64, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        if s.contains('\n') {
+            Answer::Block(s)
+        } else {
+            Answer::Text(s)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::from(s.to_string())
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self.key() == Key::Text(other)
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Signed(n) => serializer.serialize_i64(*n),
+            Answer::Unsigned(n) => serializer.serialize_u64(*n),
+            Answer::Text(s) | Answer::Block(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+struct AnswerVisitor;
+
+impl Visitor<'_> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "an integer or a string")
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Answer::Signed(v))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Answer::Unsigned(v))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Answer::from(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        // given
+        let inputs = ["24000", "-3", "CMZ", "#..#\n####"];
+
+        // when
+        let result: Vec<Answer> = inputs.iter().map(|s| s.parse().unwrap()).collect();
+
+        // then
+        assert_eq!(
+            result.iter().map(Answer::kind).collect::<Vec<_>>(),
+            vec!["unsigned", "signed", "text", "block"]
+        );
+        assert_eq!(result[0], 24000_i64);
+        assert_eq!(result[1], -3);
+        assert_eq!(result[2], "CMZ");
+    }
+
+    #[test]
+    fn numbers_equal_regardless_of_sign_test() {
+        // given
+        let signed = Answer::from(45000_i64);
+        let unsigned = Answer::from(45000_usize);
+
+        // when
+        let equal = signed == unsigned;
+
+        // then
+        assert!(equal);
+        assert_ne!(signed, Answer::from("45000"));
+    }
+
+    #[test]
+    fn display_round_trips_test() {
+        // given
+        let answers = [
+            Answer::Signed(-7),
+            Answer::from("MCD"),
+            Answer::from("#.\n.#"),
+        ];
+
+        // when
+        let result: Vec<Answer> = answers
+            .iter()
+            .map(|a| a.to_string().parse().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, answers);
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::answer::Answer;
+use crate::selection::Part;
+
+/// Known correct answers, stored as TOML with one table per day:
+///
+/// ```toml
+/// [day1]
+/// part_1 = 24000
+/// part_2 = 45000
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u8, DayAnswers>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_2: Option<Answer>,
+}
+
+impl DayAnswers {
+    fn part_mut(&mut self, part: Part) -> &mut Option<Answer> {
+        match part {
+            Part::One => &mut self.part_1,
+            Part::Two => &mut self.part_2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum Verification {
+    Pass,
+    Fail { expected: Answer },
+    Missing,
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verification::Pass => write!(f, "pass"),
+            Verification::Fail { expected } if expected.is_block() => {
+                write!(f, "FAIL, expected:\n{}", expected)
+            }
+            Verification::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Verification::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+impl Answers {
+    /// Reads answers from `path`. A file that does not exist yet holds no answers.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::from_toml(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_toml())
+    }
+
+    fn from_toml(content: &str) -> io::Result<Self> {
+        let tables: BTreeMap<String, DayAnswers> = toml::from_str(content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let days = tables
+            .into_iter()
+            .map(|(key, answers)| {
+                key.strip_prefix("day")
+                    .and_then(|day| str::parse::<u8>(day).ok())
+                    .map(|day| (day, answers))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("`{}` is not a day, expected e.g. `day1`", key),
+                        )
+                    })
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Answers { days })
+    }
+
+    fn to_toml(&self) -> String {
+        toml::to_string(self).expect("answers are always valid TOML")
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Answer> {
+        let answers = self.days.get(&day)?;
+        match part {
+            Part::One => answers.part_1.as_ref(),
+            Part::Two => answers.part_2.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, day: u8, part: Part, answer: Answer) {
+        *self.days.entry(day).or_default().part_mut(part) = Some(answer);
+    }
+
+    pub fn verify(&self, day: u8, part: Part, answer: &Answer) -> Verification {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verification::Pass,
+            Some(expected) => Verification::Fail {
+                expected: expected.clone(),
+            },
+            None => Verification::Missing,
+        }
+    }
+}
+
+impl Serialize for Answers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.days.len()))?;
+        for (day, answers) in &self.days {
+            map.serialize_entry(&format!("day{}", day), answers)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_toml_test() {
+        // given
+        let content = "[day1]
+part_1 = 24000
+part_2 = 45000
+
+[day5]
+part_1 = \"CMZ\"
+";
+
+        // when
+        let answers = Answers::from_toml(content).unwrap();
+
+        // then
+        assert_eq!(answers.get(1, Part::Two), Some(&Answer::from(45000_u64)));
+        assert_eq!(answers.get(5, Part::One), Some(&Answer::from("CMZ")));
+        assert_eq!(answers.get(5, Part::Two), None);
+    }
+
+    #[test]
+    fn to_toml_orders_days_numerically_test() {
+        // given
+        let mut answers = Answers::default();
+        answers.set(10, Part::One, Answer::from(-1_i64));
+        answers.set(2, Part::Two, Answer::from("#.\n.#"));
+
+        // when
+        let content = answers.to_toml();
+
+        // then
+        assert!(content.find("[day2]").unwrap() < content.find("[day10]").unwrap());
+        assert_eq!(Answers::from_toml(&content).unwrap(), answers);
+    }
+
+    #[test]
+    fn verify_test() {
+        // given
+        let mut answers = Answers::default();
+        answers.set(6, Part::One, Answer::from(7_usize));
+
+        // when
+        let pass = answers.verify(6, Part::One, &Answer::from(7_usize));
+        let fail = answers.verify(6, Part::One, &Answer::from(8_usize));
+        let missing = answers.verify(6, Part::Two, &Answer::from(19_usize));
+
+        // then
+        assert_eq!(pass, Verification::Pass);
+        assert_eq!(
+            fail,
+            Verification::Fail {
+                expected: Answer::from(7_usize)
+            }
+        );
+        assert_eq!(missing, Verification::Missing);
+    }
+
+    #[test]
+    fn bad_day_key_test() {
+        // given
+        let content = "[monday]\npart_1 = 1\n";
+
+        // when
+        let result = Answers::from_toml(content);
+
+        // then
+        assert!(result.is_err());
+    }
+}
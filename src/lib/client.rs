@@ -0,0 +1,400 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use ureq::Agent;
+
+use crate::answer::Answer;
+use crate::selection::Part;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const USER_AGENT: &str = concat!(
+    "github.com/arkadiuszbielewicz/advent-of-code-2022 ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoSession,
+    Status(u16),
+    UnexpectedResponse,
+    Http(ureq::Error),
+    Io(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session token, set {} or write it to a session file",
+                SESSION_ENV
+            ),
+            ClientError::Status(status) => write!(f, "server responded with status {}", status),
+            ClientError::UnexpectedResponse => {
+                write!(f, "cannot tell what the server made of the answer")
+            }
+            ClientError::Http(e) => write!(f, "request failed: {}", e),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// Reads the session token from the `AOC_SESSION` variable, or else from `session_file`.
+pub fn session(session_file: &Path) -> Result<String, ClientError> {
+    let token = match std::env::var(SESSION_ENV) {
+        Ok(token) => token,
+        Err(_) => match std::fs::read_to_string(session_file) {
+            Ok(token) => token,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(ClientError::NoSession),
+            Err(e) => return Err(e.into()),
+        },
+    };
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(ClientError::NoSession);
+    }
+    Ok(token.to_string())
+}
+
+/// Talks to the puzzle website, or to anything serving the same paths under `base_url`.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: String) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent,
+        }
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, ClientError> {
+        let mut response = self
+            .agent
+            .get(format!("{}/input", self.day_url(day)))
+            .header("Cookie", self.cookie())
+            .call()?;
+        if response.status() != 200 {
+            return Err(ClientError::Status(response.status().as_u16()));
+        }
+        Ok(response.body_mut().read_to_string()?)
+    }
+
+    pub fn submit_answer(
+        &self,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Outcome, ClientError> {
+        let mut response = self
+            .agent
+            .post(format!("{}/answer", self.day_url(day)))
+            .header("Cookie", self.cookie())
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])?;
+        if response.status() != 200 {
+            return Err(ClientError::Status(response.status().as_u16()));
+        }
+        Outcome::classify(&response.body_mut().read_to_string()?)
+            .ok_or(ClientError::UnexpectedResponse)
+    }
+}
+
+/// What the puzzle website said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Answers were submitted too recently, `wait_s` is how long to wait when the page says so.
+    RateLimited {
+        wait_s: Option<u64>,
+    },
+    /// The part was solved before, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Classifies the HTML page answering a submission, `None` when it matches no known outcome.
+    pub fn classify(html: &str) -> Option<Self> {
+        if html.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if html.contains("That's not the right answer") {
+            if html.contains("your answer is too high") {
+                Some(Outcome::TooHigh)
+            } else if html.contains("your answer is too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Wrong)
+            }
+        } else if html.contains("You gave an answer too recently") {
+            Some(Outcome::RateLimited {
+                wait_s: wait_time(html),
+            })
+        } else if html.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the website judged the answer, so that submitting it again cannot change anything.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Correct | Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong
+        )
+    }
+}
+
+/// Seconds in `You have 1m 5s left to wait`.
+fn wait_time(html: &str) -> Option<u64> {
+    let start = html.find("You have ")? + "You have ".len();
+    let end = start + html[start..].find(" left to wait")?;
+    html[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited { wait_s: Some(wait) } => {
+                write!(f, "rate limited, wait {}m {}s", wait / 60, wait % 60)
+            }
+            Outcome::RateLimited { wait_s: None } => write!(f, "rate limited"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the input of `day` is in `inputs_dir`. An input that is already there is never
+/// downloaded again.
+pub fn fetch(client: &Client, day: u8, inputs_dir: &Path) -> Result<Fetched, ClientError> {
+    let path = inputs_dir.join(format!("day{}.txt", day));
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = client.fetch_input(day)?;
+    if input.trim().is_empty() {
+        return Err(ClientError::Io(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "downloaded input is empty",
+        )));
+    }
+    std::fs::create_dir_all(inputs_dir)?;
+    std::fs::write(&path, input)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::ops::Not;
+    use std::thread::JoinHandle;
+
+    use crate::test_support::temp_dir;
+
+    /// Stand-in for the puzzle website answering requests in order with the given responses.
+    /// Returns the base URL and a handle giving back every request received, headers and body.
+    pub(crate) fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetch_downloads_once_test() {
+        // given
+        let (base_url, server) = mock_server(vec![(200, "1000\n2000\n")]);
+        let client = Client::new(&base_url, "secret".to_string());
+        let inputs_dir = temp_dir("fetch");
+
+        // when
+        let first = fetch(&client, 1, &inputs_dir).unwrap();
+        let second = fetch(&client, 1, &inputs_dir).unwrap();
+
+        // then
+        let path = inputs_dir.join("day1.txt");
+        assert_eq!(first, Fetched::Downloaded(path.clone()));
+        assert_eq!(second, Fetched::Cached(path.clone()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        let request = requests[0].to_ascii_lowercase();
+        assert!(request.starts_with("get /2022/day/1/input http/1.1\r\n"));
+        assert!(request.contains("cookie: session=secret\r\n"));
+        assert!(request.contains(&format!(
+            "user-agent: {}\r\n",
+            USER_AGENT.to_ascii_lowercase()
+        )));
+        std::fs::remove_dir_all(&inputs_dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_error_status_test() {
+        // given
+        let (base_url, server) = mock_server(vec![(404, "Not Found")]);
+        let client = Client::new(&base_url, "secret".to_string());
+        let inputs_dir = temp_dir("fetch-error");
+
+        // when
+        let result = fetch(&client, 26, &inputs_dir);
+
+        // then
+        assert!(matches!(result, Err(ClientError::Status(404))));
+        assert!(inputs_dir.join("day26.txt").exists().not());
+        server.join().unwrap();
+        std::fs::remove_dir_all(&inputs_dir).unwrap();
+    }
+
+    #[test]
+    fn classify_test() {
+        // given
+        let pages = [
+            "<main><article><p>That's the right answer! You are one gold star closer.</p>",
+            "<p>That's not the right answer; your answer is too high.  If you're stuck...</p>",
+            "<p>That's not the right answer; your answer is too low.</p>",
+            "<p>That's not the right answer.  If you're stuck, make sure...</p>",
+            "<p>You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 4m 30s left to wait.</p>",
+            "<p>You gave an answer too recently.</p>",
+            "<p>You don't seem to be solving the right level.  Did you already complete it?</p>",
+            "<p>Something else entirely</p>",
+        ];
+
+        // when
+        let result: Vec<Option<Outcome>> = pages.iter().map(|p| Outcome::classify(p)).collect();
+
+        // then
+        assert_eq!(
+            result,
+            vec![
+                Some(Outcome::Correct),
+                Some(Outcome::TooHigh),
+                Some(Outcome::TooLow),
+                Some(Outcome::Wrong),
+                Some(Outcome::RateLimited { wait_s: Some(270) }),
+                Some(Outcome::RateLimited { wait_s: None }),
+                Some(Outcome::AlreadySolved),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn submit_answer_test() {
+        // given
+        let (base_url, server) = mock_server(vec![(200, "<p>That's the right answer!</p>")]);
+        let client = Client::new(&base_url, "secret".to_string());
+
+        // when
+        let outcome = client.submit_answer(5, Part::Two, &Answer::from("CMZ"));
+
+        // then
+        assert_eq!(outcome.unwrap(), Outcome::Correct);
+        let requests = server.join().unwrap();
+        let request = requests[0].to_ascii_lowercase();
+        assert!(request.starts_with("post /2022/day/5/answer http/1.1\r\n"));
+        assert!(request.contains("cookie: session=secret\r\n"));
+        assert!(request.contains("content-type: application/x-www-form-urlencoded\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=CMZ"));
+    }
+}
@@ -1,31 +1,80 @@
-pub fn part_1(input: &str) -> i64 {
-    input
-        .split("\n\n")
-        .map(|s| {
-            s.split_whitespace()
-                .map(|s| str::parse::<i64>(s).unwrap())
-                .sum::<i64>()
-        })
-        .max()
-        .unwrap()
+use crate::error::{column, non_empty, Error, Result};
+use crate::input::Input;
+use crate::trace;
+use crate::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Parsed = Vec<i64>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    /// Sums the calories carried by every elf. Elves are separated by blank lines.
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        let mut sums = Vec::new();
+        let mut current: Option<i64> = None;
+        for (idx, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                sums.extend(current.take());
+                continue;
+            }
+            for calories in line.split_whitespace() {
+                let error = |reason: String| {
+                    Error::new(Self::DAY, reason).at(idx + 1, column(line, calories))
+                };
+                let value = str::parse::<i64>(calories).map_err(|e| {
+                    error(format!("`{}` is not a number of calories: {}", calories, e))
+                })?;
+                let sum = current
+                    .unwrap_or(0)
+                    .checked_add(value)
+                    .ok_or_else(|| error("too many calories for one elf".to_string()))?;
+                current = Some(sum);
+            }
+        }
+        sums.extend(current);
+        trace::event!(
+            debug,
+            elves = sums.len(),
+            "summed the calories of every elf"
+        );
+        non_empty(Self::DAY, "elves", sums)
+    }
+
+    fn part_1(sums: &Self::Parsed) -> Result<Self::Output1> {
+        sums.iter()
+            .max()
+            .copied()
+            .ok_or_else(|| Error::new(Self::DAY, "no elves in the input"))
+    }
+
+    fn part_2(sums: &Self::Parsed) -> Result<Self::Output2> {
+        let mut sums = sums.clone();
+        sums.sort_by(|a, b| b.cmp(a));
+        trace::event!(debug, top_three = ?&sums[..sums.len().min(3)]);
+        sums.iter()
+            .take(3)
+            .try_fold(0_i64, |acc, sum| acc.checked_add(*sum))
+            .ok_or_else(|| Error::new(Self::DAY, "top three elves carry too many calories"))
+    }
+}
+
+pub fn part_1(input: &str) -> Result<i64> {
+    Day1::part_1(&Day1::parse(&Input::from(input))?)
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let mut sums = input
-        .split("\n\n")
-        .map(|s| {
-            s.split_whitespace()
-                .map(|s| str::parse::<i64>(s).unwrap())
-                .sum::<i64>()
-        })
-        .collect::<Vec<i64>>();
-    sums.sort_by(|a, b| b.cmp(a));
-    sums.iter().take(3).sum()
+pub fn part_2(input: &str) -> Result<i64> {
+    Day1::part_2(&Day1::parse(&Input::from(input))?)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn part_1_test() {
@@ -45,7 +94,7 @@ mod test {
 
 10000";
         // when
-        let result = part_1(&input);
+        let result = part_1(input).unwrap();
 
         // then
         assert_eq!(result, 24000);
@@ -69,9 +118,75 @@ mod test {
 
 10000";
         // when
-        let result = part_2(&input);
+        let result = part_2(input).unwrap();
 
         // then
         assert_eq!(result, 45000);
     }
+
+    #[test]
+    fn crlf_line_endings_test() {
+        // given
+        let input = "1000\r\n2000\r\n3000\r\n\r\n4000\r\n\r\n5000\r\n6000\r\n";
+
+        // when
+        let result = part_1(input).unwrap();
+
+        // then
+        assert_eq!(result, 11000);
+    }
+
+    #[test]
+    fn empty_input_test() {
+        // given
+        let input = "\n\n";
+
+        // when
+        let result = part_2(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 1: no elves in the input"
+        );
+    }
+
+    #[test]
+    fn invalid_calories_test() {
+        // given
+        let input = "1000
+2000
+
+30o0";
+
+        // when
+        let result = part_1(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 1, line 4, column 1: `30o0` is not a number of calories: invalid digit found in string"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn top_three_carry_at_least_the_most_test(
+            elves in prop::collection::vec(prop::collection::vec(0..100_000_i64, 1..10), 1..50)
+        ) {
+            // given
+            let input = elves
+                .iter()
+                .map(|items| items.iter().map(i64::to_string).collect::<Vec<_>>().join("\n"))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+
+            // when
+            let most = part_1(&input).unwrap();
+            let top_three = part_2(&input).unwrap();
+
+            // then
+            prop_assert!(top_three >= most);
+        }
+    }
 }
@@ -1,34 +1,74 @@
+use std::ops::Not;
 use std::str::FromStr;
 
+use crate::error::{self, Error};
+use crate::input::Input;
+use crate::trace;
+use crate::Solution;
+
 type Score = u32;
 
-pub fn part_1(input: &str) -> Score {
-    input
-        .lines()
-        .filter_map(|line| str::parse::<Round1>(line).ok())
-        .map(|round| round.total_score())
-        .sum()
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    /// Every line read both ways: as two figures and as a figure with the expected result.
+    type Parsed = Vec<(Round1, Round2)>;
+    type Output1 = Score;
+    type Output2 = Score;
+
+    fn parse(input: &Input) -> error::Result<Self::Parsed> {
+        let rounds = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.trim().is_empty().not())
+            .map(|(idx, line)| {
+                str::parse::<Round1>(line)
+                    .ok()
+                    .zip(str::parse::<Round2>(line).ok())
+                    .ok_or_else(|| {
+                        Error::new(
+                            Self::DAY,
+                            format!("`{}` is not a round, expected e.g. `A Y`", line),
+                        )
+                        .at_line(idx + 1)
+                    })
+            })
+            .collect::<error::Result<_>>()?;
+        error::non_empty(Self::DAY, "rounds", rounds)
+    }
+
+    fn part_1(rounds: &Self::Parsed) -> error::Result<Self::Output1> {
+        Ok(rounds.iter().map(|(round, _)| round.total_score()).sum())
+    }
+
+    fn part_2(rounds: &Self::Parsed) -> error::Result<Self::Output2> {
+        Ok(rounds.iter().map(|(_, round)| round.total_score()).sum())
+    }
+}
+
+pub fn part_1(input: &str) -> error::Result<Score> {
+    Day2::part_1(&Day2::parse(&Input::from(input))?)
 }
 
-pub fn part_2(input: &str) -> Score {
-    input
-        .lines()
-        .filter_map(|line| str::parse::<Round2>(line).ok())
-        .map(|round| round.total_score())
-        .sum()
+pub fn part_2(input: &str) -> error::Result<Score> {
+    Day2::part_2(&Day2::parse(&Input::from(input))?)
 }
 
 trait Scoreable {
     fn score(&self) -> Score;
 }
 
-struct Round1 {
+pub struct Round1 {
     pub opponent: Figure,
     pub you: Figure,
 }
 
 impl Round1 {
     fn total_score(&self) -> Score {
+        trace::event!(trace, shape = self.you.score(), outcome = self.score());
         self.you.score() + self.score()
     }
 }
@@ -53,8 +93,7 @@ impl FromStr for Round1 {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s.split(' ').collect();
-        let (opponent, you) = (split[0], split[1]);
+        let (opponent, you) = s.split_once(' ').ok_or(())?;
         Ok(Self {
             opponent: str::parse::<Figure>(opponent)?,
             you: str::parse::<Figure>(you)?,
@@ -62,13 +101,14 @@ impl FromStr for Round1 {
     }
 }
 
-struct Round2 {
+pub struct Round2 {
     pub opponent: Figure,
     pub you: RoundResult,
 }
 
 impl Round2 {
     fn total_score(&self) -> Score {
+        trace::event!(trace, shape = self.you.score(), outcome = self.score());
         self.you.score() + self.score()
     }
 }
@@ -93,8 +133,7 @@ impl FromStr for Round2 {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s.split(' ').collect();
-        let (opponent, you) = (split[0], split[1]);
+        let (opponent, you) = s.split_once(' ').ok_or(())?;
         Ok(Self {
             opponent: str::parse::<Figure>(opponent)?,
             you: str::parse::<RoundResult>(you)?,
@@ -102,7 +141,7 @@ impl FromStr for Round2 {
     }
 }
 
-enum Figure {
+pub enum Figure {
     Rock,
     Paper,
     Scissors,
@@ -134,7 +173,7 @@ impl Scoreable for Figure {
     }
 }
 
-enum RoundResult {
+pub enum RoundResult {
     Lose,
     Draw,
     Win,
@@ -162,3 +201,33 @@ impl FromStr for RoundResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn rounds_score_between_1_and_9_test(
+            rounds in prop::collection::vec((0..3_u8, 0..3_u8), 1..100)
+        ) {
+            // given
+            let input: String = rounds
+                .iter()
+                .map(|(opponent, you)| {
+                    format!("{} {}\n", (b'A' + opponent) as char, (b'X' + you) as char)
+                })
+                .collect();
+            let count = rounds.len() as Score;
+
+            // when
+            let scores = [part_1(&input).unwrap(), part_2(&input).unwrap()];
+
+            // then
+            for score in scores {
+                prop_assert!((count..=9 * count).contains(&score));
+            }
+        }
+    }
+}
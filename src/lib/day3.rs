@@ -1,13 +1,98 @@
-pub fn part_1(input: &str) -> u32 {
-    input.lines().map(calculate_one_line).sum()
+use std::ops::Not;
+
+use crate::error::{non_empty, Error, Result};
+use crate::input::Input;
+use crate::trace;
+use crate::Solution;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Parsed = Vec<String>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        let rucksacks = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                if let Some((column, item)) = line
+                    .chars()
+                    .enumerate()
+                    .find(|(_, c)| c.is_ascii_alphabetic().not())
+                {
+                    return Err(Error::new(Self::DAY, format!("`{}` is not an item", item))
+                        .at(idx + 1, column + 1));
+                }
+                if line.len() % 2 != 0 {
+                    return Err(Error::new(
+                        Self::DAY,
+                        format!("{} items do not split into two compartments", line.len()),
+                    )
+                    .at_line(idx + 1));
+                }
+                Ok(line.to_string())
+            })
+            .collect::<Result<_>>()?;
+        non_empty(Self::DAY, "rucksacks", rucksacks)
+    }
+
+    fn part_1(rucksacks: &Self::Parsed) -> Result<Self::Output1> {
+        rucksacks
+            .iter()
+            .enumerate()
+            .map(|(idx, r)| {
+                calculate_one_line(r).ok_or_else(|| {
+                    Error::new(Self::DAY, "no item in both compartments").at_line(idx + 1)
+                })
+            })
+            .sum()
+    }
+
+    fn part_2(rucksacks: &Self::Parsed) -> Result<Self::Output2> {
+        rucksacks
+            .chunks(3)
+            .enumerate()
+            .map(|(idx, chunk)| {
+                let line = idx * 3 + 1;
+                match chunk {
+                    [r1, r2, r3] => trace::inspect!(
+                        find_in_group(r1, r2, r3),
+                        |badge| debug, group = idx + 1, badge = %badge
+                    )
+                    .map(to_score)
+                    .ok_or_else(|| {
+                        Error::new(Self::DAY, "no badge common to the group").at_line(line)
+                    }),
+                    _ => Err(Error::new(
+                        Self::DAY,
+                        format!("group of {} rucksacks, expected 3", chunk.len()),
+                    )
+                    .at_line(line)),
+                }
+            })
+            .sum()
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u32> {
+    Day3::part_1(&Day3::parse(&Input::from(input))?)
 }
 
-fn calculate_one_line(input: &str) -> u32 {
+fn calculate_one_line(input: &str) -> Option<u32> {
     let (first_half, second_half) = split_half(input);
-    let duplicates = find_duplicate(first_half, second_half).unwrap();
-    to_score(duplicates)
+    trace::inspect!(
+        find_duplicate(first_half, second_half),
+        |item| debug, rucksack = input, item = %item, "in both compartments"
+    )
+    .map(to_score)
 }
 
+/// Priority of an item, which has to be an ASCII letter.
 fn to_score(duplicates: char) -> u32 {
     if duplicates.is_uppercase() {
         duplicates as u32 - 38
@@ -24,16 +109,8 @@ fn find_duplicate(first: &str, second: &str) -> Option<char> {
     first.chars().find(|c| second.chars().any(|o| &o == c))
 }
 
-pub fn part_2(input: &str) -> u32 {
-    let lines: Vec<&str> = input.lines().collect();
-    lines
-        .chunks(3)
-        .map(|chunk| {
-            let (r1, r2, r3) = (chunk[0], chunk[1], chunk[2]);
-            find_in_group(r1, r2, r3).unwrap()
-        })
-        .map(to_score)
-        .sum()
+pub fn part_2(input: &str) -> Result<u32> {
+    Day3::part_2(&Day3::parse(&Input::from(input))?)
 }
 
 fn find_in_group(r1: &str, r2: &str, r3: &str) -> Option<char> {
@@ -44,6 +121,8 @@ fn find_in_group(r1: &str, r2: &str, r3: &str) -> Option<char> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
+    use proptest::prelude::*;
 
     #[test]
     fn split_in_half() {
@@ -83,7 +162,7 @@ ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
 
         // when
-        let result = part_1(input);
+        let result = part_1(input).unwrap();
 
         // then
         assert_eq!(result, 157);
@@ -100,9 +179,59 @@ ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
 
         // when
-        let result = part_2(input);
+        let result = part_2(input).unwrap();
 
         // then
         assert_eq!(result, 70);
     }
+
+    #[test]
+    fn empty_input_test() {
+        // given
+        let input = "";
+
+        // when
+        let result = part_1(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 3: no rucksacks in the input"
+        );
+    }
+
+    #[test]
+    fn incomplete_group_test() {
+        // given
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn";
+
+        // when
+        let result = part_2(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 3, line 4: group of 1 rucksacks, expected 3"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn priorities_are_between_1_and_52_test(seed in any::<u64>(), groups in 1..20_usize) {
+            // given
+            let input = generate::generate(Day3::DAY, seed, groups).unwrap();
+            let rucksacks = 3 * groups as u32;
+
+            // when
+            let shared = part_1(&input).unwrap();
+            let badges = part_2(&input).unwrap();
+
+            // then
+            prop_assert!((rucksacks..=52 * rucksacks).contains(&shared));
+            prop_assert!((groups as u32..=52 * groups as u32).contains(&badges));
+        }
+    }
 }
@@ -1,23 +1,71 @@
 use std::ops::{Not, Range};
 
-pub fn part_1(input: &str) -> u64 {
-    input
-        .lines()
-        .map(|l| l.split_once(',').unwrap())
-        .map(|(l, r)| (range_from_str(l).unwrap(), range_from_str(r).unwrap()))
-        .map(|(l, r)| is_within(&l, &r))
+use crate::error::{column, non_empty, Error, Result};
+use crate::input::Input;
+use crate::trace;
+use crate::Solution;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Parsed = Vec<(Range<u64>, Range<u64>)>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        let pairs = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                let (l, r) = line.split_once(',').ok_or_else(|| {
+                    Error::new(
+                        Self::DAY,
+                        format!("`{}` is not a pair, expected e.g. `2-4,6-8`", line),
+                    )
+                    .at_line(idx + 1)
+                })?;
+                let range = |s: &str| {
+                    range_from_str(s).ok_or_else(|| {
+                        Error::new(Self::DAY, format!("`{}` is not a range of sections", s))
+                            .at(idx + 1, column(line, s))
+                    })
+                };
+                Ok((range(l)?, range(r)?))
+            })
+            .collect::<Result<_>>()?;
+        non_empty(Self::DAY, "pairs", pairs)
+    }
+
+    fn part_1(pairs: &Self::Parsed) -> Result<Self::Output1> {
+        Ok(trace::inspect!(
+            pairs.iter(),
+            |(l, r)| trace, first = ?l, second = ?r, within = is_within(l, r)
+        )
+        .map(|(l, r)| is_within(l, r))
         .filter(|r| *r)
-        .count() as u64
-}
+        .count() as u64)
+    }
 
-pub fn part_2(input: &str) -> u64 {
-    input
-        .lines()
-        .map(|l| l.split_once(',').unwrap())
-        .map(|(l, r)| (range_from_str(l).unwrap(), range_from_str(r).unwrap()))
-        .map(|(l, r)| overlaps(&l, &r))
+    fn part_2(pairs: &Self::Parsed) -> Result<Self::Output2> {
+        Ok(trace::inspect!(
+            pairs.iter(),
+            |(l, r)| trace, first = ?l, second = ?r, overlap = overlaps(l, r)
+        )
+        .map(|(l, r)| overlaps(l, r))
         .filter(|r| *r)
-        .count() as u64
+        .count() as u64)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u64> {
+    Day4::part_1(&Day4::parse(&Input::from(input))?)
+}
+
+pub fn part_2(input: &str) -> Result<u64> {
+    Day4::part_2(&Day4::parse(&Input::from(input))?)
 }
 
 fn range_from_str(input: &str) -> Option<Range<u64>> {
@@ -28,6 +76,7 @@ fn range_from_str(input: &str) -> Option<Range<u64>> {
                 .ok()
                 .zip(str::parse::<u64>(end).ok())
         })
+        .filter(|(start, end)| start <= end)
         .map(|(start, end)| start..end)
 }
 
@@ -43,6 +92,7 @@ fn overlaps<Idx: PartialOrd<Idx>>(first: &Range<Idx>, second: &Range<Idx>) -> bo
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn is_within_test() {
@@ -74,7 +124,7 @@ mod tests {
 2-6,4-8";
 
         // when
-        let result = part_1(input);
+        let result = part_1(input).unwrap();
 
         // then
         assert_eq!(result, 2);
@@ -91,9 +141,65 @@ mod tests {
 2-6,4-8";
 
         // when
-        let result = part_2(input);
+        let result = part_2(input).unwrap();
 
         // then
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn invalid_range_test() {
+        // given
+        let input = "2-4,6-8
+2-3,4:5";
+
+        // when
+        let result = part_1(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 4, line 2, column 5: `4:5` is not a range of sections"
+        );
+
+        // given
+        let input = "2-4,6-8
+5-2,4-5";
+
+        // when
+        let result = part_2(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 4, line 2, column 1: `5-2` is not a range of sections"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn pairs_within_each_other_overlap_test(
+            pairs in prop::collection::vec(
+                ((0..100_u64, 0..100_u64), (0..100_u64, 0..100_u64)),
+                1..100
+            )
+        ) {
+            // given
+            let range = |(a, b): (u64, u64)| format!("{}-{}", a.min(b), a.max(b));
+            let input: String = pairs
+                .iter()
+                .map(|(first, second)| format!("{},{}\n", range(*first), range(*second)))
+                .collect();
+
+            // when
+            let within = part_1(&input).unwrap();
+            let overlapping = part_2(&input).unwrap();
+
+            // then
+            prop_assert!(within <= overlapping);
+            for (first, second) in Day4::parse(&Input::from(input.as_str())).unwrap() {
+                prop_assert!(is_within(&first, &second).not() || overlaps(&first, &second));
+            }
+        }
+    }
 }
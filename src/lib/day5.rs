@@ -1,4 +1,3 @@
-use std::num::TryFromIntError;
 use std::ops::Not;
 use std::str::Lines;
 
@@ -10,37 +9,101 @@ use nom::multi::separated_list0;
 use nom::sequence::{delimited, tuple};
 use nom::{sequence, IResult};
 
-pub fn part_1(input: &str) -> String {
-    let mut raw_stack: Vec<Vec<&str>> = parse_raw_stacks(input.lines());
-    let mut stack = map_raw_stocks(&mut raw_stack).unwrap();
-    let raw_commands = parse_raw_commands(input);
-    let commands = map_raw_commands(&raw_commands).unwrap();
-    commands.into_iter().for_each(|c| stack.crane_mover_9000(c));
-    stack.read_top().into_iter().collect()
+use crate::error::{column, Error, Result};
+use crate::input::Input;
+use crate::trace;
+use crate::Solution;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    type Parsed = Procedure;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        let mut raw_stack = parse_raw_stacks(input.lines())?;
+        let stack = map_raw_stocks(&mut raw_stack)?;
+        let raw_commands = parse_raw_commands(input)?;
+        let commands = map_raw_commands(&raw_commands, stack.stacks.len())?;
+        Ok(Procedure { stack, commands })
+    }
+
+    fn part_1(procedure: &Self::Parsed) -> Result<Self::Output1> {
+        let mut stack = procedure.stack.clone();
+        procedure
+            .commands
+            .iter()
+            .try_for_each(|c| stack.crane_mover_9000(*c))?;
+        Ok(stack.read_top().into_iter().collect())
+    }
+
+    fn part_2(procedure: &Self::Parsed) -> Result<Self::Output2> {
+        let mut stack = procedure.stack.clone();
+        procedure
+            .commands
+            .iter()
+            .try_for_each(|c| stack.crane_mover_9001(*c))?;
+        Ok(stack.read_top().into_iter().collect())
+    }
 }
 
-pub fn part_2(input: &str) -> String {
-    let mut raw_stack: Vec<Vec<&str>> = parse_raw_stacks(input.lines());
-    let mut stack = map_raw_stocks(&mut raw_stack).unwrap();
-    let raw_commands = parse_raw_commands(input);
-    let commands = map_raw_commands(&raw_commands).unwrap();
-    commands.into_iter().for_each(|c| stack.crane_mover_9001(c));
-    stack.read_top().into_iter().collect()
+pub fn part_1(input: &str) -> Result<String> {
+    Day5::part_1(&Day5::parse(&Input::from(input))?)
 }
 
-fn parse_raw_commands(input: &str) -> Vec<(u32, u32, u32)> {
+pub fn part_2(input: &str) -> Result<String> {
+    Day5::part_2(&Day5::parse(&Input::from(input))?)
+}
+
+/// The starting stacks together with the rearrangement to apply to them.
+pub struct Procedure {
+    stack: MultiStack,
+    commands: Vec<Command>,
+}
+
+/// Crate count, source and destination stacks, as written in a command.
+type RawCommand = (u32, u32, u32);
+
+/// Commands with the numbers of lines they were read from.
+fn parse_raw_commands(input: &str) -> Result<Vec<(usize, RawCommand)>> {
     input
         .lines()
-        .skip_while(|l| l.is_empty().not())
-        .filter(|l| l.is_empty().not())
-        .map(|l| parse_command_line(l).unwrap().1)
-        .collect::<Vec<(u32, u32, u32)>>()
+        .enumerate()
+        .skip_while(|(_, l)| l.is_empty().not())
+        .filter(|(_, l)| l.is_empty().not())
+        .map(|(idx, l)| match parse_command_line(l) {
+            Ok(("", command)) => Ok((idx + 1, command)),
+            Ok((rest, _)) => Err(Error::new(Day5::DAY, format!("unexpected `{}`", rest))
+                .at(idx + 1, column(l, rest))),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(Error::new(
+                Day5::DAY,
+                "expected a command like `move 1 from 2 to 3`",
+            )
+            .at(idx + 1, column(l, e.input))),
+            Err(nom::Err::Incomplete(_)) => {
+                Err(Error::new(Day5::DAY, "incomplete command").at_line(idx + 1))
+            }
+        })
+        .collect()
 }
 
-fn parse_raw_stacks(lines: Lines) -> Vec<Vec<&str>> {
+/// Rows of crates, from the top one down, with the numbers of lines they were read from.
+fn parse_raw_stacks(lines: Lines<'_>) -> Result<Vec<(usize, Vec<&str>)>> {
     lines
-        .take_while(|l| l.trim_start().starts_with('1').not())
-        .map(|l| parse_stack_line(l).unwrap().1)
+        .enumerate()
+        .take_while(|(_, l)| l.trim_start().starts_with('1').not())
+        .map(|(idx, l)| match parse_stack_line(l) {
+            Ok(("", crates)) => Ok((idx + 1, crates)),
+            Ok((rest, _)) | Err(nom::Err::Error(nom::error::Error { input: rest, .. })) => Err(
+                Error::new(Day5::DAY, "expected a crate like `[A]` or three spaces")
+                    .at(idx + 1, column(l, rest)),
+            ),
+            Err(_) => Err(Error::new(Day5::DAY, "expected a row of crates").at_line(idx + 1)),
+        })
         .collect()
 }
 
@@ -55,7 +118,7 @@ fn parse_stack_line(input: &str) -> IResult<&str, Vec<&str>> {
     separated_list0(complete::char(' '), parse_stack)(input)
 }
 
-fn parse_command_line(input: &str) -> IResult<&str, (u32, u32, u32)> {
+fn parse_command_line(input: &str) -> IResult<&str, RawCommand> {
     tuple((
         sequence::preceded(tag("move "), complete::u32),
         sequence::preceded(tag(" from "), complete::u32),
@@ -63,69 +126,127 @@ fn parse_command_line(input: &str) -> IResult<&str, (u32, u32, u32)> {
     ))(input)
 }
 
-fn map_raw_commands(raw_commands: &[(u32, u32, u32)]) -> Result<Vec<Command>, TryFromIntError> {
+fn map_raw_commands(raw_commands: &[(usize, RawCommand)], stacks: usize) -> Result<Vec<Command>> {
     raw_commands
         .iter()
-        .map(|(count, source, dest)| Command::new(*count, *source, *dest))
+        .map(|(line, (count, source, dest))| Command::new(*line, *count, *source, *dest, stacks))
         .collect()
 }
 
-fn map_raw_stocks(raw_stacks: &mut Vec<Vec<&str>>) -> Option<MultiStack> {
-    let mut stacks: Vec<Vec<char>> = vec![Vec::new(); raw_stacks.iter().next()?.len()];
-    while let Some(raw_stack) = raw_stacks.pop() {
+fn map_raw_stocks(raw_stacks: &mut Vec<(usize, Vec<&str>)>) -> Result<MultiStack> {
+    let width = raw_stacks
+        .iter()
+        .map(|(_, raw_stack)| raw_stack.len())
+        .max()
+        .filter(|width| *width > 0)
+        .ok_or_else(|| Error::new(Day5::DAY, "no stacks of crates"))?;
+    let mut stacks: Vec<Vec<char>> = vec![Vec::new(); width];
+    while let Some((_, raw_stack)) = raw_stacks.pop() {
         for i in 0..raw_stack.len() {
             match raw_stack[i] {
                 "   " => {}
-                s => {
-                    let x = s.chars().next().unwrap();
-                    stacks[i].push(x);
-                }
+                s => stacks[i].extend(s.chars().next()),
             }
         }
     }
 
-    Some(MultiStack { stacks })
+    Ok(MultiStack { stacks })
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 struct Command {
+    line: usize,
     size: usize,
     source: usize,
     destination: usize,
 }
 
 impl Command {
-    fn new<R>(size: R, source: R, destination: R) -> Result<Self, R::Error>
-    where
-        R: TryInto<usize>,
-    {
+    /// Command moving crates between stacks numbered from 1 up to `stacks`.
+    fn new(line: usize, size: u32, source: u32, destination: u32, stacks: usize) -> Result<Self> {
+        let index = |stack: u32| {
+            (stack as usize)
+                .checked_sub(1)
+                .filter(|index| *index < stacks)
+                .ok_or_else(|| {
+                    Error::new(
+                        Day5::DAY,
+                        format!("no stack {}, stacks are numbered 1 to {}", stack, stacks),
+                    )
+                    .at_line(line)
+                })
+        };
         Ok(Command {
-            size: size.try_into()?,
-            source: source.try_into()? - 1,
-            destination: destination.try_into()? - 1,
+            line,
+            size: size as usize,
+            source: index(source)?,
+            destination: index(destination)?,
         })
     }
+
+    fn too_many_crates(&self, left: usize) -> Error {
+        Error::new(
+            Day5::DAY,
+            format!(
+                "cannot move {} crates from stack {} holding {}",
+                self.size,
+                self.source + 1,
+                left
+            ),
+        )
+        .at_line(self.line)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct MultiStack {
     stacks: Vec<Vec<char>>,
 }
 
 impl MultiStack {
-    fn crane_mover_9000(&mut self, command: Command) {
+    fn crane_mover_9000(&mut self, command: Command) -> Result<()> {
+        if self.stacks[command.source].len() < command.size {
+            return Err(command.too_many_crates(self.stacks[command.source].len()));
+        }
         for _i in 0..command.size {
-            let v = self.stacks[command.source].pop().unwrap();
-            self.stacks[command.destination].push(v);
+            if let Some(v) = self.stacks[command.source].pop() {
+                self.stacks[command.destination].push(v);
+            }
         }
+        trace::event!(
+            debug,
+            line = command.line,
+            from = command.source + 1,
+            to = command.destination + 1,
+            moved = %self.top(command.destination, command.size)
+        );
+        Ok(())
     }
 
-    fn crane_mover_9001(&mut self, command: Command) {
+    fn crane_mover_9001(&mut self, command: Command) -> Result<()> {
         let source_vec = &mut self.stacks[command.source];
-        let drain_range = (source_vec.len().saturating_sub(command.size))..;
-        let mut drain = source_vec.drain(drain_range).collect();
+        let drain_start = source_vec
+            .len()
+            .checked_sub(command.size)
+            .ok_or_else(|| command.too_many_crates(source_vec.len()))?;
+        let mut drain = source_vec.drain(drain_start..).collect();
         let destination_vec = &mut self.stacks[command.destination];
         destination_vec.append(&mut drain);
+        trace::event!(
+            debug,
+            line = command.line,
+            from = command.source + 1,
+            to = command.destination + 1,
+            moved = %self.top(command.destination, command.size)
+        );
+        Ok(())
+    }
+
+    /// The `count` crates on top of `stack`, the lowest one first.
+    #[cfg(feature = "trace")]
+    fn top(&self, stack: usize, count: usize) -> String {
+        let crates = &self.stacks[stack];
+        crates[crates.len() - count..].iter().collect()
     }
 
     fn read_top(&mut self) -> Vec<char> {
@@ -139,6 +260,8 @@ impl MultiStack {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
+    use proptest::prelude::*;
 
     #[test]
     fn parse_stock_test() {
@@ -201,7 +324,7 @@ move 2 from 2 to 1
 move 1 from 1 to 2";
 
         // when
-        let result = part_1(input);
+        let result = part_1(input).unwrap();
 
         // then
         assert_eq!(result, "CMZ")
@@ -219,9 +342,88 @@ move 2 from 2 to 1
 move 1 from 1 to 2";
 
         // when
-        let result = part_2(input);
+        let result = part_2(input).unwrap();
+
+        // then
+        assert_eq!(result, "MCD")
+    }
+
+    #[test]
+    fn crlf_line_endings_test() {
+        // given
+        let input = "    [D]    \r\n[N] [C]    \r\n[Z] [M] [P]\r
+ 1   2   3 \r
+\r
+move 1 from 2 to 1\r
+move 3 from 1 to 3\r
+move 2 from 2 to 1\r
+move 1 from 1 to 2\r
+";
+
+        // when
+        let result = part_2(input).unwrap();
 
         // then
         assert_eq!(result, "MCD")
     }
+
+    #[test]
+    fn move_from_empty_stack_test() {
+        // given
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 4 from 1 to 3";
+
+        // when
+        let result = part_1(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 5, line 7: cannot move 4 crates from stack 1 holding 3"
+        );
+    }
+
+    #[test]
+    fn invalid_command_test() {
+        // given
+        let input = "[A] [B]
+ 1   2
+
+move 1 from 2 to 1
+move 1 form 1 to 2";
+
+        // when
+        let result = part_2(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 5, line 5, column 7: expected a command like `move 1 from 2 to 3`"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn moves_keep_every_crate_test(seed in any::<u64>(), steps in 1..50_usize) {
+            // given
+            let input = generate::generate(Day5::DAY, seed, steps).unwrap();
+            let procedure = Day5::parse(&Input::from(input.as_str())).unwrap();
+            let crates = |stack: &MultiStack| stack.stacks.iter().map(Vec::len).sum::<usize>();
+            let total = crates(&procedure.stack);
+
+            for crane in [MultiStack::crane_mover_9000, MultiStack::crane_mover_9001] {
+                let mut stack = procedure.stack.clone();
+                for command in &procedure.commands {
+                    // when
+                    crane(&mut stack, *command).unwrap();
+
+                    // then
+                    prop_assert_eq!(crates(&stack), total);
+                }
+            }
+        }
+    }
 }
@@ -1,23 +1,60 @@
 use std::ops::Not;
 
-pub fn part_1(input: &str) -> usize {
-    let window_size = 4;
-    let input = input.chars().collect::<Vec<char>>();
-    let option = input
-        .windows(window_size)
-        .enumerate()
-        .find(|w| has_duplicate(w.1).not());
-    option.unwrap().0 + window_size
+use crate::error::{non_empty, Error, Result};
+use crate::input::Input;
+use crate::trace;
+use crate::Solution;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Parsed = Vec<char>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        non_empty(Self::DAY, "datastream", input.chars().collect())
+    }
+
+    fn part_1(datastream: &Self::Parsed) -> Result<Self::Output1> {
+        find_marker(datastream, 4)
+    }
+
+    fn part_2(datastream: &Self::Parsed) -> Result<Self::Output2> {
+        find_marker(datastream, 14)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize> {
+    Day6::part_1(&Day6::parse(&Input::from(input))?)
+}
+
+pub fn part_2(input: &str) -> Result<usize> {
+    Day6::part_2(&Day6::parse(&Input::from(input))?)
 }
 
-pub fn part_2(input: &str) -> usize {
-    let window_size = 14;
-    let input = input.chars().collect::<Vec<char>>();
-    let option = input
+fn find_marker(datastream: &[char], window_size: usize) -> Result<usize> {
+    let option = datastream
         .windows(window_size)
         .enumerate()
         .find(|w| has_duplicate(w.1).not());
-    option.unwrap().0 + window_size
+    trace::inspect!(
+        option,
+        |(start, window)| debug,
+        start,
+        window = %window.iter().collect::<String>(),
+        "first window without a repeated character"
+    )
+    .map(|w| w.0 + window_size)
+    .ok_or_else(|| {
+        Error::new(
+            Day6::DAY,
+            format!("no {} different characters in a row", window_size),
+        )
+    })
 }
 
 fn has_duplicate(input: &[char]) -> bool {
@@ -34,6 +71,7 @@ fn has_duplicate(input: &[char]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn part_1_test() {
@@ -41,7 +79,7 @@ mod tests {
         let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
 
         // when
-        let result = part_1(input);
+        let result = part_1(input).unwrap();
 
         // then
         assert_eq!(result, 7);
@@ -51,7 +89,7 @@ mod tests {
         let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
 
         // when
-        let result = part_1(input);
+        let result = part_1(input).unwrap();
 
         // then
         assert_eq!(result, 11);
@@ -63,7 +101,7 @@ mod tests {
         let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
 
         // when
-        let result = part_2(input);
+        let result = part_2(input).unwrap();
 
         // then
         assert_eq!(result, 19);
@@ -73,7 +111,7 @@ mod tests {
         let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
 
         // when
-        let result = part_2(input);
+        let result = part_2(input).unwrap();
 
         // then
         assert_eq!(result, 26);
@@ -90,4 +128,51 @@ mod tests {
         // then
         assert!(result);
     }
+
+    #[test]
+    fn empty_input_test() {
+        // given
+        let input = "\n";
+
+        // when
+        let result = part_1(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 6: no datastream in the input"
+        );
+    }
+
+    #[test]
+    fn no_marker_test() {
+        // given
+        let input = "abcabcabcabc";
+
+        // when
+        let result = part_1(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 6: no 4 different characters in a row"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn message_marker_is_not_before_packet_marker_test(datastream in "[a-p]{0,200}") {
+            // given
+            let input = datastream.as_str();
+
+            // when
+            let packet = part_1(input);
+            let message = part_2(input);
+
+            // then
+            if let Ok(message) = message {
+                prop_assert!(packet.unwrap() <= message);
+            }
+        }
+    }
 }
@@ -1,41 +1,97 @@
 use grid::Grid;
 
-pub fn part_1(input: &str) -> usize {
-    let (width, grid) = input_to_grid(input);
-    grid.iter()
-        .enumerate()
-        .map(|(idx, v)| (idx_to_crd(idx, width), v))
-        .filter(|((x, y), v)| is_visible(*x, *y, **v, &grid))
-        .count()
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::trace;
+use crate::Solution;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Parsed = Grid<u32>;
+    type Output1 = usize;
+    /// Wide enough for the product of four long views of a large forest.
+    type Output2 = u64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        input_to_grid(input)
+    }
+
+    fn part_1(grid: &Self::Parsed) -> Result<Self::Output1> {
+        let width = grid.cols();
+        Ok(trace::inspect!(
+            grid.iter()
+                .enumerate()
+                .map(|(idx, v)| (idx_to_crd(idx, width), v)),
+            |((x, y), v)| trace,
+            row = x + 1,
+            column = y + 1,
+            height = **v,
+            visible = is_visible(*x, *y, **v, grid)
+        )
+        .filter(|((x, y), v)| is_visible(*x, *y, **v, grid))
+        .count())
+    }
+
+    fn part_2(grid: &Self::Parsed) -> Result<Self::Output2> {
+        let width = grid.cols();
+        trace::inspect!(
+            grid.iter()
+                .enumerate()
+                .map(|(idx, v)| (idx_to_crd(idx, width), v)),
+            |((x, y), v)| trace,
+            row = x + 1,
+            column = y + 1,
+            height = **v,
+            score = count_scenic_score(*x, *y, **v, grid)
+        )
+        .map(|((x, y), v)| count_scenic_score(x, y, *v, grid))
+        .max()
+        .ok_or_else(|| Error::new(Self::DAY, "no trees in the input"))
+    }
 }
 
-fn input_to_grid(input: &str) -> (usize, Grid<u32>) {
-    let (input, width) = input
-        .lines()
-        .map(|l| {
-            (
-                l.chars()
-                    .map(|c| c.to_digit(10).unwrap())
-                    .collect::<Vec<_>>(),
-                l.chars().count(),
-            )
-        })
-        .fold((Vec::new(), 0), |(mut acc, _), (mut line, len)| {
-            acc.append(&mut line);
-            (acc, len)
-        });
-    let grid = Grid::from_vec(input, width);
-    (width, grid)
+pub fn part_1(input: &str) -> Result<usize> {
+    Day8::part_1(&Day8::parse(&Input::from(input))?)
 }
 
-pub fn part_2(input: &str) -> u32 {
-    let (width, grid) = input_to_grid(input);
-    grid.iter()
-        .enumerate()
-        .map(|(idx, v)| (idx_to_crd(idx, width), v))
-        .map(|((x, y), v)| count_scenic_score(x, y, *v, &grid))
-        .max()
-        .unwrap()
+fn input_to_grid(input: &str) -> Result<Grid<u32>> {
+    let mut trees = Vec::new();
+    let mut width = None;
+    for (idx, l) in input.lines().enumerate() {
+        let mut row = l
+            .chars()
+            .enumerate()
+            .map(|(column, c)| {
+                c.to_digit(10).ok_or_else(|| {
+                    Error::new(Day8::DAY, format!("`{}` is not a tree height", c))
+                        .at(idx + 1, column + 1)
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        match width {
+            Some(width) if width != row.len() => {
+                return Err(Error::new(
+                    Day8::DAY,
+                    format!("row of {} trees, expected {}", row.len(), width),
+                )
+                .at_line(idx + 1));
+            }
+            _ => width = Some(row.len()),
+        }
+        trees.append(&mut row);
+    }
+    match width {
+        Some(width) if width > 0 => Ok(Grid::from_vec(trees, width)),
+        _ => Err(Error::new(Day8::DAY, "no trees in the input")),
+    }
+}
+
+pub fn part_2(input: &str) -> Result<u64> {
+    Day8::part_2(&Day8::parse(&Input::from(input))?)
 }
 
 fn idx_to_crd(idx: usize, width: usize) -> (usize, usize) {
@@ -90,8 +146,8 @@ fn is_visible(x: usize, y: usize, v: u32, grid: &Grid<u32>) -> bool {
     is_visible
 }
 
-fn count_scenic_score(x: usize, y: usize, v: u32, grid: &Grid<u32>) -> u32 {
-    let mut left_score = 0;
+fn count_scenic_score(x: usize, y: usize, v: u32, grid: &Grid<u32>) -> u64 {
+    let mut left_score = 0_u64;
     for i in (0..y).rev() {
         left_score += 1;
         if grid[x][i] >= v {
@@ -127,6 +183,7 @@ fn count_scenic_score(x: usize, y: usize, v: u32, grid: &Grid<u32>) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn part_1_test() {
@@ -138,7 +195,7 @@ mod tests {
 35390";
 
         // when
-        let result = part_1(input);
+        let result = part_1(input).unwrap();
 
         // then
         assert_eq!(result, 21);
@@ -154,9 +211,64 @@ mod tests {
 35390";
 
         // when
-        let result = part_2(input);
+        let result = part_2(input).unwrap();
 
         // then
         assert_eq!(result, 8);
     }
+
+    #[test]
+    fn part_2_large_forest_test() {
+        // given
+        let side = 601;
+        let mut rows = vec!["0".repeat(side); side];
+        rows[300].replace_range(300..301, "9");
+        let input = rows.join("\n");
+
+        // when
+        let result = part_2(&input).unwrap();
+
+        // then
+        assert_eq!(result, 300_u64.pow(4));
+    }
+
+    #[test]
+    fn ragged_grid_test() {
+        // given
+        let input = "30373
+2551
+65332";
+
+        // when
+        let result = part_1(input);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "day 8, line 2: row of 4 trees, expected 5"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn edge_trees_are_visible_test(
+            (width, trees) in (1..20_usize, 1..20_usize).prop_flat_map(|(width, height)| {
+                (Just(width), prop::collection::vec(0..10_u32, width * height))
+            })
+        ) {
+            // given
+            let grid = Grid::from_vec(trees, width);
+            let (rows, cols) = (grid.rows(), grid.cols());
+
+            for (x, y) in (0..rows).flat_map(|x| (0..cols).map(move |y| (x, y))) {
+                if x == 0 || y == 0 || x == rows - 1 || y == cols - 1 {
+                    // when
+                    let visible = is_visible(x, y, grid[x][y], &grid);
+
+                    // then
+                    prop_assert!(visible);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,157 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Failure of a single day, pointing at the place in the input that caused it when known.
+/// Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Error {
+    pub day: u8,
+    pub kind: ErrorKind,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The input could not be read or does not make sense to the solution.
+    Invalid,
+    /// There is no input to run the solution on, e.g. its file was never downloaded.
+    NoInput,
+    /// The solution panicked, the reason is the panic message.
+    Panic,
+    /// The solution ran longer than allowed and was left behind.
+    Timeout,
+}
+
+impl Error {
+    pub fn new(day: u8, reason: impl Into<String>) -> Self {
+        Error {
+            day,
+            kind: ErrorKind::Invalid,
+            line: None,
+            column: None,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn no_input(day: u8, reason: impl Into<String>) -> Self {
+        Error {
+            kind: ErrorKind::NoInput,
+            ..Error::new(day, reason)
+        }
+    }
+
+    pub fn panic(day: u8, message: impl Into<String>) -> Self {
+        Error {
+            kind: ErrorKind::Panic,
+            ..Error::new(day, message)
+        }
+    }
+
+    pub fn timeout(day: u8, reason: impl Into<String>) -> Self {
+        Error {
+            kind: ErrorKind::Timeout,
+            ..Error::new(day, reason)
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {}", self.day)?;
+        if let Some(line) = self.line {
+            write!(f, ", line {}", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        match self.kind {
+            ErrorKind::Invalid => {}
+            ErrorKind::NoInput => write!(f, ": no input")?,
+            ErrorKind::Panic => write!(f, ": panicked")?,
+            ErrorKind::Timeout => write!(f, ": timed out")?,
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Fails with a descriptive error when no valid `what` was found in the input.
+pub fn non_empty<T>(day: u8, what: &str, items: Vec<T>) -> Result<Vec<T>> {
+    if items.is_empty() {
+        Err(Error::new(day, format!("no {} in the input", what)))
+    } else {
+        Ok(items)
+    }
+}
+
+/// Column at which `part`, a subslice of `line`, starts.
+pub fn column(line: &str, part: &str) -> usize {
+    line[..(part.as_ptr() as usize - line.as_ptr() as usize)]
+        .chars()
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        // given
+        let errors = [
+            Error::new(6, "no marker found"),
+            Error::new(4, "missing `,`").at_line(3),
+            Error::new(8, "`x` is not a tree height").at(2, 5),
+            Error::no_input(7, "input/day7.txt does not exist"),
+            Error::panic(6, "index out of bounds"),
+            Error::timeout(8, "still running after 1.00s"),
+        ];
+
+        // when
+        let result: Vec<String> = errors.iter().map(Error::to_string).collect();
+
+        // then
+        assert_eq!(
+            result,
+            vec![
+                "day 6: no marker found",
+                "day 4, line 3: missing `,`",
+                "day 8, line 2, column 5: `x` is not a tree height",
+                "day 7: no input: input/day7.txt does not exist",
+                "day 6: panicked: index out of bounds",
+                "day 8: timed out: still running after 1.00s",
+            ]
+        );
+    }
+
+    #[test]
+    fn column_test() {
+        // given
+        let line = "move 1 from x to 2";
+
+        // when
+        let result = column(line, &line[12..]);
+
+        // then
+        assert_eq!(result, 13);
+    }
+}
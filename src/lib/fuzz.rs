@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+use std::ops::{Not, Range};
+use std::panic;
+use std::sync::Once;
+use std::thread;
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{RngExt, SeedableRng};
+
+use crate::error::{Error, ErrorKind};
+use crate::generate;
+use crate::input::Input;
+use crate::runner::{self, DayRun, Options};
+use crate::DynSolution;
+
+/// Pieces of text that mean something to at least one of the parsers.
+const TOKENS: &[&str] = &[
+    "\n",
+    "\n\n",
+    " ",
+    "   ",
+    "-",
+    ",",
+    "[",
+    "]",
+    "[A]",
+    "move ",
+    " from ",
+    " to ",
+    "0",
+    "1",
+    "9",
+    "-1",
+    "99999999999999999999",
+    "A",
+    "Z",
+    "X",
+    "a",
+    "é",
+    "\t",
+    "\r",
+    "\u{feff}",
+];
+
+/// Input that made a day panic, or run into the timeout, shrunk for as long as it keeps doing so.
+#[derive(Debug, Clone)]
+pub struct Crash {
+    pub day: u8,
+    pub case: u64,
+    pub input: String,
+    pub error: Error,
+}
+
+/// Input of fuzz case `case` of `day`: mostly a generated input with a few random edits, sometimes
+/// random bytes.
+pub fn mutate(day: u8, case: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(case);
+    let generated = generate::generate(day, case, rng.random_range(1..=20));
+    let Some(generated) = generated.filter(|_| rng.random_bool(0.8)) else {
+        let bytes: Vec<u8> = (0..rng.random_range(0..=64))
+            .map(|_| rng.random())
+            .collect();
+        return String::from_utf8_lossy(&bytes).into_owned();
+    };
+    let mut text: Vec<char> = generated.chars().collect();
+    for _ in 0..rng.random_range(1..=4) {
+        let at = rng.random_range(0..=text.len());
+        let end = (at + rng.random_range(1..=8)).min(text.len());
+        match rng.random_range(0..5) {
+            0 => {
+                let token = TOKENS.choose(&mut rng).unwrap();
+                text.splice(at..at, token.chars());
+            }
+            1 => {
+                text.drain(at..end);
+            }
+            2 => {
+                let copy: Vec<char> = text[at..end].to_vec();
+                text.splice(at..at, copy);
+            }
+            3 => text.truncate(at),
+            _ => {
+                let mut lines: Vec<String> = text
+                    .iter()
+                    .collect::<String>()
+                    .lines()
+                    .map(str::to_string)
+                    .collect();
+                lines.shuffle(&mut rng);
+                text = lines.join("\n").chars().collect();
+            }
+        }
+    }
+    text.into_iter().collect()
+}
+
+/// The panic or timeout of `run`, if it had one.
+fn crash_of(run: &DayRun) -> Option<&Error> {
+    run.error
+        .iter()
+        .chain(run.parts.iter().filter_map(|p| p.answer.as_ref().err()))
+        .find(|e| matches!(e.kind, ErrorKind::Panic | ErrorKind::Timeout))
+}
+
+/// Runs parsing and both parts of `solution` on the input of every case, and returns the first
+/// crash of every distinct panic message.
+pub fn fuzz(
+    solution: &'static dyn DynSolution,
+    cases: Range<u64>,
+    options: &Options,
+) -> Vec<Crash> {
+    let day = solution.day();
+    let crash =
+        |input: &str| crash_of(&runner::run_with(solution, &Input::from(input), options)).cloned();
+    let mut seen = HashSet::new();
+    let mut crashes = Vec::new();
+    for case in cases {
+        let input = mutate(day, case);
+        let Some(error) = crash(&input) else {
+            continue;
+        };
+        if seen.insert(error.reason.clone()).not() {
+            continue;
+        }
+        // every try of a timeout waits for it, so only panics are shrunk
+        let input = match error.kind {
+            ErrorKind::Panic => shrink(&input, |candidate| {
+                crash(candidate).is_some_and(|e| e.reason == error.reason)
+            }),
+            _ => input,
+        };
+        crashes.push(Crash {
+            day,
+            case,
+            input,
+            error,
+        });
+    }
+    crashes
+}
+
+/// Keeps the panics of the `day N` threads the runner solves on quiet, as the fuzz loop reports
+/// them as crashes, and passes every other panic on to the hook installed before.
+pub fn silence_day_panics() {
+    static SILENCED: Once = Once::new();
+    SILENCED.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let on_day = thread::current()
+                .name()
+                .is_some_and(|name| name.starts_with("day "));
+            if on_day.not() {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Drops whole lines, then single characters, from `input` for as long as `still_fails`.
+pub(crate) fn shrink(input: &str, mut still_fails: impl FnMut(&str) -> bool) -> String {
+    let mut input = input.to_string();
+    loop {
+        let lines: Vec<&str> = input.lines().collect();
+        let without_line = (0..lines.len()).map(|i| {
+            let mut fewer = lines.clone();
+            fewer.remove(i);
+            fewer.join("\n")
+        });
+        let without_char = input.char_indices().map(|(i, c)| {
+            let mut shorter = input.clone();
+            shorter.replace_range(i..i + c.len_utf8(), "");
+            shorter
+        });
+        match without_line
+            .chain(without_char)
+            .find(|candidate| still_fails(candidate))
+        {
+            Some(smaller) => input = smaller,
+            None => return input,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn no_day_panics_test() {
+        silence_day_panics();
+        for solution in crate::SOLUTIONS {
+            // given
+            let cases = 0..500;
+            let options = Options {
+                timeout: Some(Duration::from_secs(5)),
+                ..Options::default()
+            };
+
+            // when
+            let crashes = fuzz(*solution, cases, &options);
+
+            // then
+            assert!(crashes.is_empty(), "{:#?}", crashes);
+        }
+    }
+
+    #[test]
+    fn mutate_is_reproducible_test() {
+        // given
+        let case = 7;
+
+        // when
+        let first = mutate(5, case);
+        let second = mutate(5, case);
+
+        // then
+        assert_eq!(first, second);
+        assert_ne!(first, mutate(5, case + 1));
+    }
+
+    #[test]
+    fn shrink_test() {
+        // given
+        let input = "12\n\n345\nx67\n8";
+
+        // when
+        let shrunk = shrink(input, |candidate| candidate.contains('x'));
+
+        // then
+        assert_eq!(shrunk, "x");
+    }
+}
@@ -0,0 +1,240 @@
+use std::ops::Not;
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{RngExt, SeedableRng};
+
+/// Writes a random input of the given size.
+type Generator = fn(&mut StdRng, usize) -> String;
+
+/// Every day with a generator, in order. What the size counts differs per day.
+static GENERATORS: &[(u8, Generator)] = &[
+    // elves
+    (1, calories),
+    // rounds
+    (2, strategy_guide),
+    // groups of three rucksacks
+    (3, rucksacks),
+    // pairs of elves
+    (4, section_pairs),
+    // rearrangement steps
+    (5, crane_procedure),
+    // characters, at least 14
+    (6, datastream),
+    // trees along each side of the square
+    (8, tree_grid),
+];
+
+/// Days that have a generator.
+pub fn days() -> Vec<u8> {
+    GENERATORS.iter().map(|(day, _)| *day).collect()
+}
+
+/// A valid random input of `day`, the same for the same `seed` and `size`.
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    let (_, generator) = GENERATORS.iter().find(|(d, _)| *d == day)?;
+    Some(generator(&mut StdRng::seed_from_u64(seed), size.max(1)))
+}
+
+fn calories(rng: &mut StdRng, elves: usize) -> String {
+    let elves: Vec<String> = (0..elves)
+        .map(|_| {
+            let items: Vec<String> = (0..rng.random_range(1..=10))
+                .map(|_| rng.random_range(1000..=60000_i64).to_string())
+                .collect();
+            items.join("\n")
+        })
+        .collect();
+    elves.join("\n\n") + "\n"
+}
+
+fn strategy_guide(rng: &mut StdRng, rounds: usize) -> String {
+    (0..rounds)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                ['A', 'B', 'C'].choose(rng).unwrap(),
+                ['X', 'Y', 'Z'].choose(rng).unwrap()
+            )
+        })
+        .collect()
+}
+
+/// Rucksacks with one item type in both compartments, and one badge type common to each group.
+fn rucksacks(rng: &mut StdRng, groups: usize) -> String {
+    let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut lines = String::new();
+    for _ in 0..groups {
+        items.shuffle(rng);
+        let (badge, others) = items.split_first().unwrap();
+        // every elf packs its own items, so only the badge is shared by the group
+        for own in others.chunks(others.len() / 3) {
+            let shared = if rng.random_bool(1.0 / 3.0) {
+                *badge
+            } else {
+                own[0]
+            };
+            let (left_only, right_only) = own[1..].split_at(own.len() / 2);
+            let extra = rng.random_range(0..=15);
+            let mut left = vec![shared];
+            let mut right = vec![shared];
+            left.extend((0..extra).map(|_| *left_only.choose(rng).unwrap()));
+            right.extend((0..extra).map(|_| *right_only.choose(rng).unwrap()));
+            if shared != *badge {
+                left.push(*badge);
+                right.push(*right_only.choose(rng).unwrap());
+            }
+            left.shuffle(rng);
+            right.shuffle(rng);
+            lines.extend(left.into_iter().chain(right));
+            lines.push('\n');
+        }
+    }
+    lines
+}
+
+fn section_pairs(rng: &mut StdRng, pairs: usize) -> String {
+    let mut range = || {
+        let start = rng.random_range(1..=99);
+        format!("{}-{}", start, rng.random_range(start..=99))
+    };
+    (0..pairs)
+        .map(|_| format!("{},{}\n", range(), range()))
+        .collect()
+}
+
+/// Starting stacks and steps that never take more crates than a stack holds.
+fn crane_procedure(rng: &mut StdRng, steps: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..rng.random_range(2..=9))
+        .map(|_| {
+            (0..rng.random_range(1..=8))
+                .map(|_| rng.random_range('A'..='Z'))
+                .collect()
+        })
+        .collect();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or_default();
+    let mut drawing = String::new();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_string(),
+            })
+            .collect();
+        drawing.push_str(&row.join(" "));
+        drawing.push('\n');
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!(" {} ", n)).collect();
+    drawing.push_str(&numbers.join(" "));
+    drawing.push_str("\n\n");
+
+    for _ in 0..steps {
+        let sources: Vec<usize> = (0..stacks.len())
+            .filter(|i| stacks[*i].is_empty().not())
+            .collect();
+        let source = *sources.choose(rng).unwrap();
+        let destination = (source + rng.random_range(1..stacks.len())) % stacks.len();
+        let count = rng.random_range(1..=stacks[source].len());
+        let from = stacks[source].len() - count;
+        let moved: Vec<char> = stacks[source].drain(from..).collect();
+        stacks[destination].extend(moved);
+        drawing.push_str(&format!(
+            "move {} from {} to {}\n",
+            count,
+            source + 1,
+            destination + 1
+        ));
+    }
+    drawing
+}
+
+/// Letters with 14 different ones in a row somewhere, so both markers exist.
+fn datastream(rng: &mut StdRng, length: usize) -> String {
+    let length = length.max(14);
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    let mut stream: Vec<char> = (0..length).map(|_| *letters.choose(rng).unwrap()).collect();
+    letters.shuffle(rng);
+    let marker = rng.random_range(0..=length - 14);
+    stream[marker..marker + 14].copy_from_slice(&letters[..14]);
+    stream.into_iter().chain(std::iter::once('\n')).collect()
+}
+
+fn tree_grid(rng: &mut StdRng, side: usize) -> String {
+    (0..side)
+        .map(|_| {
+            let mut row: String = (0..side)
+                .map(|_| char::from(b'0' + rng.random_range(0..=9)))
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+    use crate::runner::run;
+    use crate::selection::Parts;
+
+    #[test]
+    fn generated_inputs_are_solved_test() {
+        for day in days() {
+            for (seed, size) in [(0, 1), (1, 2), (2, 50)] {
+                // given
+                let input = generate(day, seed, size).unwrap();
+
+                // when
+                let run = run(
+                    crate::solution(day).unwrap(),
+                    &Input::from(input.as_str()),
+                    Parts::Both,
+                    1,
+                );
+
+                // then
+                assert!(
+                    run.is_ok(),
+                    "day {}, seed {}, size {}: {:?}\n{}",
+                    day,
+                    seed,
+                    size,
+                    run,
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generate_is_reproducible_test() {
+        // given
+        let seed = 42;
+
+        // when
+        let first = generate(5, seed, 20);
+        let second = generate(5, seed, 20);
+
+        // then
+        assert_eq!(first, second);
+        assert_ne!(first, generate(5, seed + 1, 20));
+        assert_eq!(generate(7, seed, 20), None);
+    }
+
+    #[test]
+    fn size_test() {
+        // given
+        let size = 30;
+
+        // when
+        let trees = generate(8, 0, size).unwrap();
+        let rucksacks = generate(3, 0, size).unwrap();
+
+        // then
+        assert_eq!(trees.lines().count(), size);
+        assert!(trees.lines().all(|l| l.len() == size));
+        assert_eq!(rucksacks.lines().count(), 3 * size);
+    }
+}
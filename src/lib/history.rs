@@ -0,0 +1,372 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{format_duration, DayRun};
+use crate::selection::Part;
+
+/// Median times of a single invocation, stored as one JSON line of the history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Commit the solutions were built from, ending in `-dirty` with uncommitted changes.
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub mode: Mode,
+    pub days: Vec<DayTimes>,
+}
+
+/// How the days of an entry were run. Only the times of entries run the same way compare.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Mode {
+    /// `debug` or `release`, empty for entries from before the profile was recorded.
+    pub profile: String,
+    /// Directory the inputs were read from.
+    pub inputs_dir: PathBuf,
+    /// Input set the days ran on, `None` for the inputs directory itself.
+    pub set: Option<String>,
+    pub parallel: bool,
+    pub repeat: usize,
+    pub timeout_ns: Option<u64>,
+}
+
+impl Mode {
+    /// Profile the running binary was built with.
+    pub fn profile() -> &'static str {
+        if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        }
+    }
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Mode {
+            profile: String::new(),
+            inputs_dir: PathBuf::from("input"),
+            set: None,
+            parallel: false,
+            repeat: 1,
+            timeout_ns: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayTimes {
+    pub day: u8,
+    pub parse_ns: u64,
+    pub part_1_ns: Option<u64>,
+    pub part_2_ns: Option<u64>,
+}
+
+impl HistoryEntry {
+    /// Entry with the days of `runs` that ran without errors.
+    pub fn new(commit: String, mode: Mode, runs: &[&DayRun]) -> Self {
+        let nanos = |run: &DayRun, part| {
+            run.part(part)
+                .filter(|p| p.answer.is_ok())
+                .map(|p| p.timing.median.as_nanos() as u64)
+        };
+        let days = runs
+            .iter()
+            .filter(|run| run.error.is_none())
+            .map(|run| DayTimes {
+                day: run.day,
+                parse_ns: run.parse.median.as_nanos() as u64,
+                part_1_ns: nanos(run, Part::One),
+                part_2_ns: nanos(run, Part::Two),
+            })
+            .collect();
+        HistoryEntry {
+            commit,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            mode,
+            days,
+        }
+    }
+}
+
+/// Hash of the checked out commit, or `unknown` outside of a git repository.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) if git(&["status", "--porcelain"]).is_some_and(|s| s.is_empty().not()) => {
+            format!("{}-dirty", hash)
+        }
+        Some(hash) => hash,
+        None => "unknown".to_string(),
+    }
+}
+
+pub fn append(path: &Path, entry: &HistoryEntry) -> io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let line = serde_json::to_string(entry).expect("history entries serialize to JSON");
+    writeln!(file, "{}", line)
+}
+
+/// Reads every entry of the history at `path`, oldest first.
+pub fn load(path: &Path) -> io::Result<Vec<HistoryEntry>> {
+    std::fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.trim().is_empty().not())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", i + 1, e))
+            })
+        })
+        .collect()
+}
+
+/// Latest of the `earlier` entries run the same way as `latest`, from a commit starting with
+/// `commit` when given.
+pub fn baseline<'a>(
+    earlier: &'a [HistoryEntry],
+    latest: &HistoryEntry,
+    commit: Option<&str>,
+) -> Option<&'a HistoryEntry> {
+    earlier
+        .iter()
+        .rev()
+        .filter(|e| e.mode == latest.mode)
+        .find(|e| commit.is_none_or(|commit| e.commit.starts_with(commit)))
+}
+
+/// Time of the same step in two entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub day: u8,
+    pub step: &'static str,
+    pub baseline: Duration,
+    pub latest: Duration,
+}
+
+impl Change {
+    /// How much slower the latest time is, in percent of the baseline; negative when faster.
+    pub fn percent(&self) -> f64 {
+        let baseline = self.baseline.max(Duration::from_nanos(1));
+        (self.latest.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.percent() > threshold_percent
+    }
+}
+
+/// Every step timed in both entries.
+pub fn compare(baseline: &HistoryEntry, latest: &HistoryEntry) -> Vec<Change> {
+    let steps = |times: &DayTimes| {
+        [
+            ("parse", Some(times.parse_ns)),
+            ("part 1", times.part_1_ns),
+            ("part 2", times.part_2_ns),
+        ]
+    };
+    let mut changes = Vec::new();
+    for times in &latest.days {
+        let Some(before) = baseline.days.iter().find(|d| d.day == times.day) else {
+            continue;
+        };
+        for ((step, before), (_, after)) in steps(before).into_iter().zip(steps(times)) {
+            if let (Some(before), Some(after)) = (before, after) {
+                changes.push(Change {
+                    day: times.day,
+                    step,
+                    baseline: Duration::from_nanos(before),
+                    latest: Duration::from_nanos(after),
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Table of `changes`, flagging the steps more than `threshold_percent` slower.
+pub fn diff_table(changes: &[Change], threshold_percent: f64) -> String {
+    let mut table = String::new();
+    let _ = writeln!(
+        table,
+        "Day  Step      {:>10} {:>10} {:>8}",
+        "Baseline", "Latest", "Change"
+    );
+    for change in changes {
+        let _ = writeln!(
+            table,
+            "{:>3}  {:<8}  {:>10} {:>10} {:>+7.1}%{}",
+            change.day,
+            change.step,
+            format_duration(change.baseline),
+            format_duration(change.latest),
+            change.percent(),
+            if change.is_regression(threshold_percent) {
+                "  SLOWER"
+            } else {
+                ""
+            }
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+    use crate::runner::run;
+    use crate::selection::Parts;
+    use crate::test_support::temp_dir;
+
+    fn entry(commit: &str, days: &[(u8, u64, Option<u64>, Option<u64>)]) -> HistoryEntry {
+        HistoryEntry {
+            commit: commit.to_string(),
+            timestamp: 0,
+            mode: Mode {
+                profile: "release".to_string(),
+                ..Mode::default()
+            },
+            days: days
+                .iter()
+                .map(|&(day, parse_ns, part_1_ns, part_2_ns)| DayTimes {
+                    day,
+                    parse_ns,
+                    part_1_ns,
+                    part_2_ns,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn new_entry_skips_failed_days_test() {
+        // given
+        let day_4 = crate::solution(4).unwrap();
+        let ok = run(day_4, &Input::from("2-4,6-8"), Parts::One, 1);
+        let failed = run(day_4, &Input::from("2-4"), Parts::Both, 1);
+
+        // when
+        let entry = HistoryEntry::new("abc1234".to_string(), Mode::default(), &[&ok, &failed]);
+
+        // then
+        assert_eq!(entry.days.len(), 1);
+        assert!(entry.days[0].part_1_ns.is_some());
+        assert_eq!(entry.days[0].part_2_ns, None);
+    }
+
+    #[test]
+    fn append_and_load_test() {
+        // given
+        let dir = temp_dir("history");
+        let path = dir.join("perf-history.jsonl");
+        let mut parallel = entry("def5678-dirty", &[(8, 10, None, Some(30))]);
+        parallel.mode = Mode {
+            profile: "debug".to_string(),
+            inputs_dir: PathBuf::from("inputs"),
+            set: Some("alt".to_string()),
+            parallel: true,
+            repeat: 5,
+            timeout_ns: Some(500_000_000),
+        };
+        let entries = [
+            entry("abc1234", &[(6, 100, Some(200), Some(300))]),
+            parallel,
+        ];
+
+        // when
+        for entry in &entries {
+            append(&path, entry).unwrap();
+        }
+        let loaded = load(&path).unwrap();
+
+        // then
+        assert_eq!(loaded, entries);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_entry_without_mode_test() {
+        // given
+        let dir = temp_dir("history-without-mode");
+        let path = dir.join("perf-history.jsonl");
+        std::fs::write(&path, r#"{"commit":"abc1234","timestamp":0,"days":[]}"#).unwrap();
+
+        // when
+        let loaded = load(&path).unwrap();
+
+        // then
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].mode, Mode::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn baseline_test() {
+        // given
+        let mut repeated = entry("c", &[]);
+        repeated.mode.repeat = 10;
+        let mut debug = entry("d", &[]);
+        debug.mode.profile = "debug".to_string();
+        let earlier = [entry("a", &[]), entry("b", &[]), repeated, debug];
+        let latest = entry("e", &[]);
+
+        // when
+        let previous = baseline(&earlier, &latest, None);
+        let of_commit = baseline(&earlier, &latest, Some("a"));
+        let of_other_mode = baseline(&earlier, &latest, Some("c"));
+        let of_other_profile = baseline(&earlier, &latest, Some("d"));
+
+        // then
+        assert_eq!(previous.map(|e| e.commit.as_str()), Some("b"));
+        assert_eq!(of_commit.map(|e| e.commit.as_str()), Some("a"));
+        assert_eq!(of_other_mode, None);
+        assert_eq!(of_other_profile, None);
+    }
+
+    #[test]
+    fn compare_test() {
+        // given
+        let baseline = entry("a", &[(6, 1000, Some(2000), None), (8, 1000, None, None)]);
+        let latest = entry(
+            "b",
+            &[(6, 1050, Some(3000), Some(500)), (1, 10, None, None)],
+        );
+
+        // when
+        let changes = compare(&baseline, &latest);
+
+        // then
+        let regressions: Vec<(u8, &str, bool)> = changes
+            .iter()
+            .map(|c| (c.day, c.step, c.is_regression(10.0)))
+            .collect();
+        assert_eq!(regressions, vec![(6, "parse", false), (6, "part 1", true)]);
+        assert_eq!(
+            diff_table(&changes, 10.0),
+            "Day  Step        Baseline     Latest   Change
+  6  parse          1.0µs      1.1µs    +5.0%
+  6  part 1         2.0µs      3.0µs   +50.0%  SLOWER
+"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::{self, Error};
+
+/// Puzzle input as every solution sees it: without a byte order mark, with `\n` line endings, no
+/// whitespace at the end of lines and no blank lines at the end, whichever machine saved it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input(String);
+
+impl Input {
+    pub fn new(raw: &str) -> Self {
+        let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+        let mut lines: Vec<&str> = raw
+            .split("\r\n")
+            .flat_map(|l| l.split(['\r', '\n']))
+            .map(str::trim_end)
+            .collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        Input(lines.join("\n"))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<&str> for Input {
+    fn from(raw: &str) -> Self {
+        Input::new(raw)
+    }
+}
+
+/// Where the puzzle input of a day comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// The default input of `day`, `dayN.txt` in `inputs_dir`.
+    pub fn for_day(inputs_dir: &Path, day: u8) -> Self {
+        InputSource::File(inputs_dir.join(format!("day{}.txt", day)))
+    }
+
+    pub fn load(&self) -> io::Result<Input> {
+        let raw = match self {
+            InputSource::File(path) => std::fs::read_to_string(path)?,
+            InputSource::Stdin => {
+                let mut raw = String::new();
+                io::stdin().read_to_string(&mut raw)?;
+                raw
+            }
+        };
+        Ok(Input::new(&raw))
+    }
+
+    /// Loads the input of `day`, reporting a missing or empty file as no input.
+    pub fn load_for_day(&self, day: u8) -> error::Result<Input> {
+        match self.load() {
+            Ok(input) if input.is_empty() => {
+                Err(Error::no_input(day, format!("{} is empty", self)))
+            }
+            Ok(input) => Ok(input),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(Error::no_input(day, format!("{} does not exist", self)))
+            }
+            Err(e) => Err(Error::new(day, format!("cannot read {}: {}", self, e))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    /// `-` stands for the standard input, anything else is a path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("input path cannot be empty".to_string()),
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn normalize_line_endings_test() {
+        // given
+        let inputs = [
+            "1000\n2000\n\n3000\n",
+            "1000\r\n2000\r\n\r\n3000\r\n",
+            "1000\r2000\r\r3000\r",
+        ];
+
+        // when
+        let result: Vec<Input> = inputs.iter().map(|s| Input::new(s)).collect();
+
+        // then
+        assert!(result.iter().all(|i| i.as_str() == "1000\n2000\n\n3000"));
+    }
+
+    #[test]
+    fn normalize_bom_and_trailing_whitespace_test() {
+        // given
+        let raw = "\u{feff}    [D]    \n[N] [C]  \t\n\n  \n\n";
+
+        // when
+        let input = Input::new(raw);
+
+        // then
+        assert_eq!(input.as_str(), "    [D]\n[N] [C]");
+    }
+
+    #[test]
+    fn load_missing_and_empty_test() {
+        // given
+        let dir = temp_dir("input");
+        std::fs::write(dir.join("day6.txt"), "\r\n\n").unwrap();
+
+        // when
+        let empty = InputSource::for_day(&dir, 6).load_for_day(6);
+        let missing = InputSource::for_day(&dir, 7).load_for_day(7);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // then
+        assert_eq!(empty.unwrap_err().kind, error::ErrorKind::NoInput);
+        assert_eq!(missing.unwrap_err().kind, error::ErrorKind::NoInput);
+    }
+
+    #[test]
+    fn parse_source_test() {
+        // given
+        let inputs = ["-", "other/day3.txt"];
+
+        // when
+        let result: Vec<InputSource> = inputs.iter().map(|s| s.parse().unwrap()).collect();
+
+        // then
+        assert_eq!(
+            result,
+            vec![
+                InputSource::Stdin,
+                InputSource::File(PathBuf::from("other/day3.txt"))
+            ]
+        );
+    }
+
+    #[test]
+    fn for_day_test() {
+        // given
+        let inputs_dir = Path::new("inputs");
+
+        // when
+        let source = InputSource::for_day(inputs_dir, 8);
+
+        // then
+        assert_eq!(source.to_string(), "inputs/day8.txt");
+    }
+}
@@ -1,3 +1,13 @@
+use std::any::Any;
+
+use answer::Answer;
+use error::Result;
+use input::Input;
+use selection::Part;
+
+pub mod answer;
+pub mod answers;
+pub mod client;
 pub mod day1;
 pub mod day2;
 pub mod day3;
@@ -5,3 +15,122 @@ pub mod day4;
 pub mod day5;
 pub mod day6;
 pub mod day8;
+pub mod error;
+pub mod fuzz;
+pub mod generate;
+pub mod history;
+pub mod input;
+pub mod output;
+#[cfg(test)]
+mod reference;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod selection;
+pub mod sets;
+pub mod submissions;
+#[cfg(test)]
+mod test_support;
+pub mod trace;
+pub mod watch;
+
+/// A single day of the puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Parsed;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed>;
+    fn part_1(input: &Self::Parsed) -> Result<Self::Output1>;
+    fn part_2(input: &Self::Parsed) -> Result<Self::Output2>;
+}
+
+/// Object safe view of a [`Solution`], so that days with different types can live in one list.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    /// Parses `input` into something both parts can share, also across threads.
+    fn parse_input(&self, input: &Input) -> Result<Box<dyn Any + Send + Sync>>;
+    /// Solves `part` for input returned by [`DynSolution::parse_input`] of the same day.
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Parsed: Send + Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse_input(&self, input: &Input) -> Result<Box<dyn Any + Send + Sync>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Parsed>()
+            .expect("input parsed by another day");
+        match part {
+            Part::One => S::part_1(input).map(Into::into),
+            Part::Two => S::part_2(input).map(Into::into),
+        }
+    }
+}
+
+/// Every implemented day, in order.
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day8::Day8,
+];
+
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_are_ordered_by_day_test() {
+        // given
+        let days: Vec<u8> = SOLUTIONS.iter().map(|s| s.day()).collect();
+
+        // when
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+
+        // then
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn solve_through_registry_test() {
+        // given
+        let day_6 = solution(6).unwrap();
+        let input = Input::from("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n");
+        let input = day_6.parse_input(&input).unwrap();
+
+        // when
+        let result = day_6.solve(input.as_ref(), Part::Two).unwrap();
+
+        // then
+        assert_eq!(result, 19);
+        assert!(solution(7).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::answer::Answer;
+use crate::answers::Verification;
+use crate::error::Error;
+use crate::input::InputSource;
+use crate::runner::{DayRun, Timing};
+
+/// Bumped whenever a field is renamed or removed, so that consumers can tell outputs apart.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human readable text with a timing table
+    Text,
+    /// A single JSON document with every day
+    Json,
+    /// One JSON object per day and line
+    Jsonl,
+}
+
+/// All runs of a single invocation. `total_ns` sums the time of every day, `wall_ns` is how
+/// long running all of them took, shorter than the sum when days run in parallel.
+#[derive(Debug, Serialize)]
+pub struct RunRecord<'a> {
+    pub version: u32,
+    pub days: Vec<DayRecord<'a>>,
+    pub total_ns: u64,
+    pub wall_ns: u64,
+}
+
+impl<'a> RunRecord<'a> {
+    pub fn new(days: Vec<DayRecord<'a>>, wall: Duration) -> Self {
+        let total_ns = days.iter().map(|d| d.total_ns).sum();
+        RunRecord {
+            version: SCHEMA_VERSION,
+            days,
+            total_ns,
+            wall_ns: wall.as_nanos() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayRecord<'a> {
+    pub version: u32,
+    pub day: u8,
+    pub title: &'a str,
+    pub input: String,
+    pub parse: DurationRecord,
+    pub parts: Vec<PartRecord<'a>>,
+    pub error: Option<&'a Error>,
+    pub total_ns: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartRecord<'a> {
+    pub part: u8,
+    pub answer: Option<&'a Answer>,
+    #[serde(rename = "type")]
+    pub kind: Option<&'a str>,
+    pub duration: DurationRecord,
+    pub error: Option<&'a Error>,
+    pub verification: Option<&'a Verification>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DurationRecord {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl From<&Timing> for DurationRecord {
+    fn from(timing: &Timing) -> Self {
+        DurationRecord {
+            min_ns: timing.min.as_nanos() as u64,
+            median_ns: timing.median.as_nanos() as u64,
+            max_ns: timing.max.as_nanos() as u64,
+        }
+    }
+}
+
+impl<'a> DayRecord<'a> {
+    /// Record of `run`, with the verification of every part in the same order as its parts.
+    pub fn new(
+        run: &'a DayRun,
+        source: &InputSource,
+        verifications: &'a [Option<Verification>],
+    ) -> Self {
+        let parts = run
+            .parts
+            .iter()
+            .zip(verifications.iter().chain(std::iter::repeat(&None)))
+            .map(|(part, verification)| PartRecord {
+                part: part.part.number(),
+                answer: part.answer.as_ref().ok(),
+                kind: part.answer.as_ref().ok().map(Answer::kind),
+                duration: DurationRecord::from(&part.timing),
+                error: part.answer.as_ref().err(),
+                verification: verification.as_ref(),
+            })
+            .collect();
+        DayRecord {
+            version: SCHEMA_VERSION,
+            day: run.day,
+            title: run.title,
+            input: source.to_string(),
+            parse: DurationRecord::from(&run.parse),
+            parts,
+            error: run.error.as_ref(),
+            total_ns: run.total().as_nanos() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+    use crate::selection::Parts;
+    use std::path::PathBuf;
+
+    #[test]
+    fn day_record_test() {
+        // given
+        let solution = crate::solution(4).unwrap();
+        let input = Input::from("2-4,6-8\n2-8,3-7");
+        let run = crate::runner::run(solution, &input, Parts::Both, 1);
+        let source = InputSource::File(PathBuf::from("input/day4.txt"));
+        let verifications = [Some(Verification::Pass), Some(Verification::Missing)];
+
+        // when
+        let record = DayRecord::new(&run, &source, &verifications);
+        let json = serde_json::to_value(&record).unwrap();
+
+        // then
+        assert_eq!(json["day"], 4);
+        assert_eq!(json["input"], "input/day4.txt");
+        assert_eq!(json["error"], serde_json::Value::Null);
+        assert_eq!(json["parts"][0]["answer"], 1);
+        assert_eq!(json["parts"][0]["type"], "unsigned");
+        assert_eq!(json["parts"][0]["verification"]["status"], "pass");
+        assert_eq!(json["parts"][1]["answer"], 1);
+        assert_eq!(json["parts"][1]["verification"]["status"], "missing");
+    }
+
+    #[test]
+    fn failed_day_record_test() {
+        // given
+        let solution = crate::solution(4).unwrap();
+        let input = Input::from("2-4,6-8\n2-8");
+        let run = crate::runner::run(solution, &input, Parts::Both, 1);
+        let source = InputSource::Stdin;
+
+        // when
+        let record = DayRecord::new(&run, &source, &[]);
+        let json = serde_json::to_value(&record).unwrap();
+
+        // then
+        assert_eq!(json["input"], "stdin");
+        assert_eq!(json["parts"], serde_json::json!([]));
+        assert_eq!(json["error"]["line"], 2);
+        assert_eq!(json["error"]["column"], serde_json::Value::Null);
+    }
+}
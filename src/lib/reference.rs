@@ -0,0 +1,486 @@
+//! Deliberately naive solutions of every part, to cross-check the real ones on generated inputs.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+
+use crate::fuzz;
+use crate::generate;
+use crate::input::Input;
+use crate::selection::Part;
+
+/// Slow but plainly correct answer of one part, `None` for input it does not understand.
+type Reference = fn(&str) -> Option<String>;
+
+static REFERENCES: &[(u8, Part, Reference)] = &[
+    (1, Part::One, day1_part_1),
+    (1, Part::Two, day1_part_2),
+    (2, Part::One, day2_part_1),
+    (2, Part::Two, day2_part_2),
+    (3, Part::One, day3_part_1),
+    (3, Part::Two, day3_part_2),
+    (4, Part::One, day4_part_1),
+    (4, Part::Two, day4_part_2),
+    (5, Part::One, day5_part_1),
+    (5, Part::Two, day5_part_2),
+    (6, Part::One, day6_part_1),
+    (6, Part::Two, day6_part_2),
+    (8, Part::One, day8_part_1),
+    (8, Part::Two, day8_part_2),
+];
+
+/// How the real solution differed from the reference on one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mismatch {
+    Wrong { expected: String, actual: String },
+    Failed { expected: String, error: String },
+}
+
+/// Smallest input found on which a part and its reference do not agree.
+#[derive(Debug)]
+struct Disagreement {
+    day: u8,
+    part: Part,
+    seed: u64,
+    size: usize,
+    input: String,
+    mismatch: Mismatch,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} part {}, seed {} size {}: ",
+            self.day, self.part, self.seed, self.size
+        )?;
+        match &self.mismatch {
+            Mismatch::Wrong { expected, actual } => {
+                write!(f, "answered {:?}, expected {:?}", actual, expected)?
+            }
+            Mismatch::Failed { expected, error } => {
+                write!(f, "failed with `{}`, expected {:?}", error, expected)?
+            }
+        }
+        write!(f, ", shrunk to the input\n{}", self.input)
+    }
+}
+
+/// Compares the real solution with `reference`, ignoring input that either of them rejects.
+fn check(day: u8, part: Part, reference: Reference, input: &str) -> Option<Mismatch> {
+    let input = Input::from(input);
+    let expected = reference(&input)?;
+    let solution = crate::solution(day).expect("references exist for implemented days");
+    let parsed = solution.parse_input(&input).ok()?;
+    match solution.solve(parsed.as_ref(), part) {
+        Ok(answer) if answer.to_string() == expected => None,
+        Ok(answer) => Some(Mismatch::Wrong {
+            expected,
+            actual: answer.to_string(),
+        }),
+        Err(e) => Some(Mismatch::Failed {
+            expected,
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Tries generated inputs from the smallest size up, and shrinks the first one that disagrees.
+fn find_disagreement(
+    day: u8,
+    part: Part,
+    reference: Reference,
+    sizes: std::ops::RangeInclusive<usize>,
+    seeds: u64,
+) -> Option<Disagreement> {
+    for size in sizes {
+        for seed in 0..seeds {
+            let input = generate::generate(day, seed, size).expect("day has a generator");
+            if let Some(mismatch) = check(day, part, reference, &input) {
+                let (input, mismatch) = shrink(day, part, reference, input, mismatch);
+                return Some(Disagreement {
+                    day,
+                    part,
+                    seed,
+                    size,
+                    input,
+                    mismatch,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Shrinks `input` for as long as the same kind of mismatch remains.
+fn shrink(
+    day: u8,
+    part: Part,
+    reference: Reference,
+    input: String,
+    mismatch: Mismatch,
+) -> (String, Mismatch) {
+    let kind = std::mem::discriminant(&mismatch);
+    let input = fuzz::shrink(&input, |candidate| {
+        check(day, part, reference, candidate).is_some_and(|m| std::mem::discriminant(&m) == kind)
+    });
+    let mismatch = check(day, part, reference, &input).unwrap_or(mismatch);
+    (input, mismatch)
+}
+
+fn day1_elves(input: &str) -> Option<Vec<i64>> {
+    let mut elves = Vec::new();
+    for group in input.split("\n\n").filter(|g| g.trim().is_empty().not()) {
+        let mut sum = 0_i64;
+        for line in group.lines() {
+            sum = sum.checked_add(line.parse().ok()?)?;
+        }
+        elves.push(sum);
+    }
+    Some(elves)
+}
+
+fn day1_part_1(input: &str) -> Option<String> {
+    day1_elves(input)?.into_iter().max().map(|m| m.to_string())
+}
+
+fn day1_part_2(input: &str) -> Option<String> {
+    let mut elves = day1_elves(input)?;
+    elves.sort();
+    elves.reverse();
+    elves.truncate(3);
+    elves
+        .into_iter()
+        .try_fold(0_i64, i64::checked_add)
+        .map(|sum| sum.to_string())
+}
+
+/// Rounds as pairs of shapes numbered 0 for rock, 1 for paper and 2 for scissors.
+fn day2_rounds(input: &str) -> Option<Vec<(usize, usize)>> {
+    input
+        .lines()
+        .filter(|l| l.is_empty().not())
+        .map(|line| match line.as_bytes() {
+            [opponent @ b'A'..=b'C', b' ', you @ b'X'..=b'Z'] => {
+                Some(((opponent - b'A') as usize, (you - b'X') as usize))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// 0 when you lose the round, 1 for a draw and 2 when you win.
+fn day2_outcome(opponent: usize, you: usize) -> usize {
+    let beats = |a: usize, b: usize| matches!((a, b), (0, 2) | (1, 0) | (2, 1));
+    if beats(you, opponent) {
+        2
+    } else if beats(opponent, you) {
+        0
+    } else {
+        1
+    }
+}
+
+fn day2_score(opponent: usize, you: usize) -> u32 {
+    (you + 1 + 3 * day2_outcome(opponent, you)) as u32
+}
+
+fn day2_part_1(input: &str) -> Option<String> {
+    let rounds = day2_rounds(input)?;
+    let total: u32 = rounds.iter().map(|(o, y)| day2_score(*o, *y)).sum();
+    rounds.is_empty().not().then(|| total.to_string())
+}
+
+fn day2_part_2(input: &str) -> Option<String> {
+    let rounds = day2_rounds(input)?;
+    let total: u32 = rounds
+        .iter()
+        .map(|(opponent, outcome)| {
+            // try every shape until one ends the round as asked
+            let you = (0..3)
+                .find(|you| day2_outcome(*opponent, *you) == *outcome)
+                .unwrap();
+            day2_score(*opponent, you)
+        })
+        .sum();
+    rounds.is_empty().not().then(|| total.to_string())
+}
+
+fn day3_priority(item: char) -> u32 {
+    let items: String = ('a'..='z').chain('A'..='Z').collect();
+    items.find(item).unwrap() as u32 + 1
+}
+
+fn day3_rucksacks(input: &str) -> Option<Vec<&str>> {
+    let rucksacks: Vec<&str> = input.lines().collect();
+    let valid = |r: &&str| r.chars().all(|c| c.is_ascii_alphabetic()) && r.len().is_multiple_of(2);
+    (rucksacks.is_empty().not() && rucksacks.iter().all(valid)).then_some(rucksacks)
+}
+
+fn day3_part_1(input: &str) -> Option<String> {
+    let mut total = 0;
+    for rucksack in day3_rucksacks(input)? {
+        let (left, right) = rucksack.split_at(rucksack.len() / 2);
+        let shared = left.chars().find(|c| right.contains(*c))?;
+        total += day3_priority(shared);
+    }
+    Some(total.to_string())
+}
+
+fn day3_part_2(input: &str) -> Option<String> {
+    let rucksacks = day3_rucksacks(input)?;
+    if rucksacks.len().is_multiple_of(3).not() {
+        return None;
+    }
+    let mut total = 0;
+    for group in rucksacks.chunks(3) {
+        let badge = group[0]
+            .chars()
+            .find(|c| group[1].contains(*c) && group[2].contains(*c))?;
+        total += day3_priority(badge);
+    }
+    Some(total.to_string())
+}
+
+/// Every pair as the sets of sections both elves clean.
+fn day4_pairs(input: &str) -> Option<Vec<(HashSet<u64>, HashSet<u64>)>> {
+    let sections = |s: &str| {
+        let (start, end) = s.split_once('-')?;
+        let (start, end): (u64, u64) = (start.parse().ok()?, end.parse().ok()?);
+        (start <= end).then(|| (start..=end).collect::<HashSet<u64>>())
+    };
+    let pairs: Vec<_> = input
+        .lines()
+        .map(|line| {
+            let (left, right) = line.split_once(',')?;
+            Some((sections(left)?, sections(right)?))
+        })
+        .collect::<Option<_>>()?;
+    pairs.is_empty().not().then_some(pairs)
+}
+
+fn day4_part_1(input: &str) -> Option<String> {
+    let count = day4_pairs(input)?
+        .iter()
+        .filter(|(l, r)| l.is_subset(r) || r.is_subset(l))
+        .count();
+    Some(count.to_string())
+}
+
+fn day4_part_2(input: &str) -> Option<String> {
+    let count = day4_pairs(input)?
+        .iter()
+        .filter(|(l, r)| l.intersection(r).next().is_some())
+        .count();
+    Some(count.to_string())
+}
+
+/// Stacks from the bottom crate up, and moves as count, source and destination indices.
+type Day5Procedure = (Vec<Vec<char>>, Vec<(usize, usize, usize)>);
+
+fn day5_procedure(input: &str) -> Option<Day5Procedure> {
+    let (drawing, moves) = input.split_once("\n\n")?;
+    let mut rows: Vec<&str> = drawing.lines().collect();
+    let numbers = rows.pop()?;
+    let count = numbers.split_whitespace().count();
+    let expected: Vec<String> = (1..=count).map(|n| format!(" {} ", n)).collect();
+    if count == 0 || numbers != expected.join(" ").trim_end() {
+        return None;
+    }
+
+    let mut stacks = vec![Vec::new(); count];
+    let mut widest = 0;
+    for row in rows.iter().rev() {
+        let cells: Vec<char> = row.chars().collect();
+        let cells: Vec<&[char]> = cells.chunks(4).collect();
+        widest = widest.max(cells.len());
+        for (stack, cell) in cells.iter().enumerate() {
+            match cell {
+                ['[', c, ']'] | ['[', c, ']', ' '] if c.is_ascii_uppercase() => {
+                    stacks.get_mut(stack)?.push(*c)
+                }
+                [' ', ' ', ' '] | [' ', ' ', ' ', ' '] => {}
+                _ => return None,
+            }
+        }
+    }
+    if widest != count {
+        return None;
+    }
+
+    let mut moves_read = Vec::new();
+    for line in moves.lines() {
+        let words: Vec<&str> = line.split(' ').collect();
+        let ["move", n, "from", source, "to", destination] = words[..] else {
+            return None;
+        };
+        let index = |s: &str| {
+            s.parse::<usize>()
+                .ok()?
+                .checked_sub(1)
+                .filter(|i| *i < count)
+        };
+        moves_read.push((n.parse().ok()?, index(source)?, index(destination)?));
+    }
+    Some((stacks, moves_read))
+}
+
+fn day5_tops(stacks: &[Vec<char>]) -> String {
+    stacks
+        .iter()
+        .map(|s| s.last().copied().unwrap_or(' '))
+        .collect()
+}
+
+fn day5_part_1(input: &str) -> Option<String> {
+    let (mut stacks, moves) = day5_procedure(input)?;
+    for (n, source, destination) in moves {
+        for _ in 0..n {
+            let c = stacks[source].pop()?;
+            stacks[destination].push(c);
+        }
+    }
+    Some(day5_tops(&stacks))
+}
+
+fn day5_part_2(input: &str) -> Option<String> {
+    let (mut stacks, moves) = day5_procedure(input)?;
+    for (n, source, destination) in moves {
+        // moving the crates one by one twice keeps them in order
+        let mut lifted = Vec::new();
+        for _ in 0..n {
+            lifted.push(stacks[source].pop()?);
+        }
+        while let Some(c) = lifted.pop() {
+            stacks[destination].push(c);
+        }
+    }
+    Some(day5_tops(&stacks))
+}
+
+fn day6_marker(input: &str, size: usize) -> Option<String> {
+    let stream: Vec<char> = input.chars().collect();
+    (size..=stream.len())
+        .find(|end| {
+            stream[end - size..*end]
+                .iter()
+                .collect::<BTreeSet<_>>()
+                .len()
+                == size
+        })
+        .map(|end| end.to_string())
+}
+
+fn day6_part_1(input: &str) -> Option<String> {
+    day6_marker(input, 4)
+}
+
+fn day6_part_2(input: &str) -> Option<String> {
+    day6_marker(input, 14)
+}
+
+fn day8_grid(input: &str) -> Option<Vec<Vec<u32>>> {
+    let grid: Vec<Vec<u32>> = input
+        .lines()
+        .map(|line| line.chars().map(|c| c.to_digit(10)).collect())
+        .collect::<Option<_>>()?;
+    let width = grid.first()?.len();
+    (width > 0 && grid.iter().all(|row| row.len() == width)).then_some(grid)
+}
+
+/// Heights of the trees seen from the tree at `row` and `col`, looking up, down, left and right.
+fn day8_views(grid: &[Vec<u32>], row: usize, col: usize) -> [Vec<u32>; 4] {
+    [
+        (0..row).rev().map(|r| grid[r][col]).collect(),
+        (row + 1..grid.len()).map(|r| grid[r][col]).collect(),
+        (0..col).rev().map(|c| grid[row][c]).collect(),
+        (col + 1..grid[row].len()).map(|c| grid[row][c]).collect(),
+    ]
+}
+
+fn day8_part_1(input: &str) -> Option<String> {
+    let grid = day8_grid(input)?;
+    let mut visible = 0;
+    for row in 0..grid.len() {
+        for col in 0..grid[row].len() {
+            let height = grid[row][col];
+            if day8_views(&grid, row, col)
+                .iter()
+                .any(|view| view.iter().all(|h| *h < height))
+            {
+                visible += 1;
+            }
+        }
+    }
+    Some(visible.to_string())
+}
+
+fn day8_part_2(input: &str) -> Option<String> {
+    let grid = day8_grid(input)?;
+    let mut best = 0_u64;
+    for row in 0..grid.len() {
+        for col in 0..grid[row].len() {
+            let height = grid[row][col];
+            let score: u64 = day8_views(&grid, row, col)
+                .iter()
+                .map(|view| match view.iter().position(|h| *h >= height) {
+                    Some(blocking) => blocking as u64 + 1,
+                    None => view.len() as u64,
+                })
+                .product();
+            best = best.max(score);
+        }
+    }
+    Some(best.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_agree_with_references_test() {
+        for (day, part, reference) in REFERENCES {
+            // given
+            let (sizes, seeds) = (1..=12, 20);
+
+            // when
+            let disagreement = find_disagreement(*day, *part, *reference, sizes, seeds);
+
+            // then
+            if let Some(disagreement) = disagreement {
+                panic!("{}", disagreement);
+            }
+        }
+    }
+
+    #[test]
+    fn references_cover_every_generator_test() {
+        // given
+        let days = generate::days();
+
+        // when
+        let covered: BTreeSet<u8> = REFERENCES.iter().map(|(day, _, _)| *day).collect();
+
+        // then
+        assert_eq!(covered.into_iter().collect::<Vec<_>>(), days);
+    }
+
+    #[test]
+    fn shrinks_disagreement_test() {
+        // given
+        let smallest_elf = |input: &str| -> Option<String> {
+            day1_elves(input)?.into_iter().min().map(|m| m.to_string())
+        };
+
+        // when
+        let disagreement = find_disagreement(1, Part::One, smallest_elf, 1..=5, 5).unwrap();
+
+        // then
+        assert!(matches!(disagreement.mismatch, Mismatch::Wrong { .. }));
+        assert_eq!(disagreement.size, 2);
+        let elves: Vec<&str> = disagreement.input.split("\n\n").collect();
+        assert_eq!(elves.len(), 2);
+        assert!(elves.iter().all(|elf| elf.len() == 1), "{}", disagreement);
+    }
+}
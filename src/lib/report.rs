@@ -0,0 +1,285 @@
+use std::fmt::Write;
+use std::ops::Not;
+
+use crate::answer::Answer;
+use crate::answers::Verification;
+use crate::runner::{format_duration, DayRun};
+use crate::selection::{Part, LAST_DAY};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Markdown,
+    /// A single page with its styles included
+    Html,
+}
+
+/// Everything the report says about one day. `run` is `None` for days without a module.
+#[derive(Debug)]
+pub struct ReportRow {
+    pub day: u8,
+    pub run: Option<DayRun>,
+    /// Verification of every part in the same order as the parts of `run`.
+    pub verifications: Vec<Option<Verification>>,
+    /// Lines of code of the module, without blank lines and comments.
+    pub lines: Option<usize>,
+}
+
+/// Lines in `source` that are neither blank nor only a comment.
+pub fn lines_of_code(source: &str) -> usize {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.is_empty().not() && l.starts_with("//").not())
+        .count()
+}
+
+pub fn render(rows: &[ReportRow], format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => markdown(rows),
+        ReportFormat::Html => html(rows),
+    }
+}
+
+const HEADERS: [&str; 7] = [
+    "Day", "Title", "Part 1", "Part 2", "Status", "Time", "Lines",
+];
+
+/// Text of every cell of `row`, with answers run through `code` so each format can mark them up.
+fn cells(row: &ReportRow, code: impl Fn(&Answer) -> String) -> [String; 7] {
+    let lines = row.lines.map_or("-".to_string(), |l| l.to_string());
+    let Some(run) = &row.run else {
+        let dash = || "-".to_string();
+        return [
+            row.day.to_string(),
+            dash(),
+            dash(),
+            dash(),
+            "missing".to_string(),
+            dash(),
+            lines,
+        ];
+    };
+    let part = |part: Part| {
+        let Some(index) = run.parts.iter().position(|p| p.part == part) else {
+            return "-".to_string();
+        };
+        let answer = match &run.parts[index].answer {
+            Ok(answer) => code(answer),
+            Err(_) => return "error".to_string(),
+        };
+        match row.verifications.get(index).and_then(Option::as_ref) {
+            Some(Verification::Pass) => format!("{} ✓", answer),
+            Some(Verification::Fail { expected }) => {
+                format!("{} ✗ expected {}", answer, code(expected))
+            }
+            Some(Verification::Missing) | None => answer,
+        }
+    };
+    [
+        row.day.to_string(),
+        run.title.to_string(),
+        part(Part::One),
+        part(Part::Two),
+        status(row).to_string(),
+        format_duration(run.total()),
+        lines,
+    ]
+}
+
+fn status(row: &ReportRow) -> &'static str {
+    let wrong = row
+        .verifications
+        .iter()
+        .any(|v| matches!(v, Some(Verification::Fail { .. })));
+    match &row.run {
+        None => "missing",
+        Some(run) if run.has_no_input() => "no input",
+        Some(run) if run.is_ok().not() => "failed",
+        Some(_) if wrong => "wrong",
+        Some(_) if stars(row) == 2 => "solved",
+        Some(_) => "unverified",
+    }
+}
+
+/// Parts whose answer matches the stored one.
+fn stars(row: &ReportRow) -> usize {
+    row.verifications
+        .iter()
+        .filter(|v| matches!(v, Some(Verification::Pass)))
+        .count()
+}
+
+fn progress(rows: &[ReportRow]) -> String {
+    format!(
+        "{} of {} days implemented, {} of {} stars verified.",
+        rows.iter().filter(|r| r.run.is_some()).count(),
+        LAST_DAY,
+        rows.iter().map(stars).sum::<usize>(),
+        2 * LAST_DAY as usize
+    )
+}
+
+fn markdown(rows: &[ReportRow]) -> String {
+    let code = |answer: &Answer| {
+        let lines: Vec<String> = answer
+            .to_string()
+            .lines()
+            .map(|l| l.replace('|', "\\|"))
+            .collect();
+        if answer.is_block() {
+            format!("<code>{}</code>", lines.join("<br>"))
+        } else {
+            format!("`{}`", lines.join(""))
+        }
+    };
+    let mut page = String::new();
+    let _ = writeln!(page, "# Advent of Code 2022\n\n{}\n", progress(rows));
+    let _ = writeln!(page, "| {} |", HEADERS.join(" | "));
+    let _ = writeln!(page, "|{}", "---|".repeat(HEADERS.len()));
+    for row in rows {
+        let _ = writeln!(page, "| {} |", cells(row, code).join(" | "));
+    }
+    page
+}
+
+fn html(rows: &[ReportRow]) -> String {
+    let code = |answer: &Answer| format!("<code>{}</code>", escape(&answer.to_string()));
+    let mut page = String::new();
+    let _ = write!(
+        page,
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Advent of Code 2022</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }}
+code {{ white-space: pre; }}
+.solved {{ background: #e6ffe6; }}
+.wrong, .failed {{ background: #ffe6e6; }}
+.missing, .no-input {{ color: #999; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code 2022</h1>
+<p>{}</p>
+<table>
+<tr>{}</tr>
+",
+        progress(rows),
+        HEADERS.map(|h| format!("<th>{}</th>", h)).concat()
+    );
+    for row in rows {
+        let class = status(row).replace(' ', "-");
+        let cells = cells(row, code);
+        let _ = writeln!(
+            page,
+            "<tr class=\"{}\">{}</tr>",
+            class,
+            cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| match i {
+                    // answers are escaped when marked up
+                    2 | 3 => format!("<td>{}</td>", cell),
+                    _ => format!("<td>{}</td>", escape(cell)),
+                })
+                .collect::<String>()
+        );
+    }
+    page.push_str("</table>\n</body>\n</html>\n");
+    page
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+    use crate::runner::run;
+    use crate::selection::Parts;
+
+    fn rows() -> Vec<ReportRow> {
+        let day_5 = crate::solution(5).unwrap();
+        let input = Input::from("    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1");
+        vec![
+            ReportRow {
+                day: 5,
+                run: Some(run(day_5, &input, Parts::Both, 1)),
+                verifications: vec![
+                    Some(Verification::Pass),
+                    Some(Verification::Fail {
+                        expected: Answer::from("A|B"),
+                    }),
+                ],
+                lines: Some(120),
+            },
+            ReportRow {
+                day: 7,
+                run: None,
+                verifications: Vec::new(),
+                lines: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn lines_of_code_test() {
+        // given
+        let source = "// comment\nfn main() {\n\n    println!();\n    // inner\n}\n";
+
+        // when
+        let result = lines_of_code(source);
+
+        // then
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn markdown_test() {
+        // given
+        let rows = rows();
+
+        // when
+        let page = render(&rows, ReportFormat::Markdown);
+
+        // then
+        let lines: Vec<&str> = page.lines().collect();
+        assert_eq!(
+            lines[2],
+            "1 of 25 days implemented, 1 of 50 stars verified."
+        );
+        assert_eq!(
+            lines[4],
+            "| Day | Title | Part 1 | Part 2 | Status | Time | Lines |"
+        );
+        assert!(lines[6]
+            .starts_with("| 5 | Supply Stacks | `DC` ✓ | `DC` ✗ expected `A\\|B` | wrong | "));
+        assert!(lines[6].ends_with(" | 120 |"));
+        assert_eq!(lines[7], "| 7 | - | - | - | missing | - | - |");
+    }
+
+    #[test]
+    fn html_test() {
+        // given
+        let rows = rows();
+
+        // when
+        let page = render(&rows, ReportFormat::Html);
+
+        // then
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<tr class=\"wrong\"><td>5</td><td>Supply Stacks</td>"));
+        assert!(page.contains("<td><code>DC</code> ✓</td>"));
+        assert!(page.contains("<tr class=\"missing\"><td>7</td>"));
+        assert!(page.ends_with("</html>\n"));
+    }
+}
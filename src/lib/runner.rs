@@ -0,0 +1,474 @@
+use std::any::Any;
+use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::answer::Answer;
+use crate::error::{Error, ErrorKind, Result};
+use crate::input::Input;
+use crate::selection::{Part, Parts};
+use crate::DynSolution;
+
+/// Wall-clock time of a step repeated one or more times.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn once(duration: Duration) -> Self {
+        Timing {
+            min: duration,
+            median: duration,
+            max: duration,
+        }
+    }
+
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Timing {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Result<Answer>,
+    pub timing: Timing,
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: u8,
+    pub title: &'static str,
+    pub parse: Timing,
+    pub parts: Vec<PartRun>,
+    /// Failure that kept any part from running, e.g. unreadable input.
+    pub error: Option<Error>,
+}
+
+impl DayRun {
+    pub fn failed(solution: &dyn DynSolution, error: Error) -> Self {
+        DayRun {
+            day: solution.day(),
+            title: solution.title(),
+            parse: Timing::default(),
+            parts: Vec::new(),
+            error: Some(error),
+        }
+    }
+
+    /// Whether the day either ran without errors or had no input to run on.
+    pub fn is_ok(&self) -> bool {
+        (self.error.is_none() || self.has_no_input()) && self.parts.iter().all(|p| p.answer.is_ok())
+    }
+
+    pub fn has_no_input(&self) -> bool {
+        self.error
+            .as_ref()
+            .is_some_and(|e| e.kind == ErrorKind::NoInput)
+    }
+
+    pub fn part(&self, part: Part) -> Option<&PartRun> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    /// Median time of parsing plus every part that was run.
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.parts.iter().map(|p| p.timing.median).sum::<Duration>()
+    }
+}
+
+/// How [`run_with`] runs a day.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub parts: Parts,
+    /// Times to run every step, to tell its min, median and max time.
+    pub repeat: usize,
+    /// Solve the parts at the same time on the rayon thread pool.
+    pub parallel: bool,
+    /// Longest a step, repeats included, may run. A step running longer is reported as timed
+    /// out and left to finish on its own thread.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            parts: Parts::Both,
+            repeat: 1,
+            parallel: false,
+            timeout: None,
+        }
+    }
+}
+
+/// Parses `input` and solves the selected parts, running each step `repeat` times.
+pub fn run(
+    solution: &'static dyn DynSolution,
+    input: &Input,
+    parts: Parts,
+    repeat: usize,
+) -> DayRun {
+    let options = Options {
+        parts,
+        repeat,
+        ..Options::default()
+    };
+    run_with(solution, input, &options)
+}
+
+/// Parses `input` and solves the selected parts. A step that panics or times out fails on its
+/// own, without taking the caller down.
+pub fn run_with(solution: &'static dyn DynSolution, input: &Input, options: &Options) -> DayRun {
+    let day = solution.day();
+    let repeat = options.repeat.max(1);
+    let input = Arc::new(input.clone());
+    let (parsed, parse) = isolate(day, repeat, options.timeout, move || {
+        #[cfg(feature = "trace")]
+        let _span = tracing::info_span!("parse", day).entered();
+        solution.parse_input(&input).map(Arc::from)
+    });
+    let mut run = DayRun {
+        day,
+        title: solution.title(),
+        parse,
+        parts: Vec::new(),
+        error: None,
+    };
+    match parsed {
+        Ok(parsed) => {
+            let solve = |part: Part| {
+                let parsed: Arc<dyn Any + Send + Sync> = Arc::clone(&parsed);
+                let (answer, timing) = isolate(day, repeat, options.timeout, move || {
+                    #[cfg(feature = "trace")]
+                    let _span = tracing::info_span!("solve", day, part = %part).entered();
+                    solution.solve(parsed.as_ref(), part)
+                });
+                PartRun {
+                    part,
+                    answer,
+                    timing,
+                }
+            };
+            let selected: Vec<Part> = [Part::One, Part::Two]
+                .into_iter()
+                .filter(|part| options.parts.contains(*part))
+                .collect();
+            run.parts = if options.parallel {
+                selected.into_par_iter().map(solve).collect()
+            } else {
+                selected.into_iter().map(solve).collect()
+            };
+        }
+        Err(e) => run.error = Some(e),
+    }
+    run
+}
+
+/// Measures `step` of `day`, turning a panic into an error. With a `timeout` the step runs on a
+/// thread of its own, and is given up on once it runs longer.
+fn isolate<T: Send + 'static>(
+    day: u8,
+    repeat: usize,
+    timeout: Option<Duration>,
+    step: impl Fn() -> Result<T> + Send + 'static,
+) -> (Result<T>, Timing) {
+    let start = Instant::now();
+    let measured = move || panic::catch_unwind(AssertUnwindSafe(|| measure(repeat, &step)));
+    let outcome = match timeout {
+        None => measured(),
+        Some(timeout) => {
+            let (sender, receiver) = mpsc::channel();
+            let spawned = thread::Builder::new()
+                .name(format!("day {}", day))
+                .spawn(move || sender.send(measured()));
+            if let Err(e) = spawned {
+                let error = Error::new(day, format!("cannot start a thread: {}", e));
+                return (Err(error), Timing::default());
+            }
+            match receiver.recv_timeout(timeout) {
+                Ok(outcome) => outcome,
+                Err(_) => {
+                    let reason = format!("still running after {}", format_duration(timeout));
+                    return (
+                        Err(Error::timeout(day, reason)),
+                        Timing::once(start.elapsed()),
+                    );
+                }
+            }
+        }
+    };
+    outcome.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|m| m.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "no message".to_string());
+        (
+            Err(Error::panic(day, message)),
+            Timing::once(start.elapsed()),
+        )
+    })
+}
+
+fn measure<T>(repeat: usize, mut f: impl FnMut() -> T) -> (T, Timing) {
+    let mut samples = Vec::with_capacity(repeat);
+    let mut result = None;
+    for _ in 0..repeat {
+        let start = Instant::now();
+        result = Some(f());
+        samples.push(start.elapsed());
+    }
+    (result.unwrap(), Timing::from_samples(samples))
+}
+
+/// Table with the time of every step of every day, and the sum of all of them.
+pub fn summary(runs: &[&DayRun]) -> String {
+    let detailed = runs
+        .iter()
+        .flat_map(|r| std::iter::once(&r.parse).chain(r.parts.iter().map(|p| &p.timing)))
+        .any(|t| t.min != t.max);
+    let width = if detailed { 30 } else { 10 };
+    let cell = |timing: Option<&Timing>, error: Option<&Error>| match (timing, error) {
+        (_, Some(e)) => match e.kind {
+            ErrorKind::Invalid => "error",
+            ErrorKind::NoInput => "no input",
+            ErrorKind::Panic => "panicked",
+            ErrorKind::Timeout => "timed out",
+        }
+        .to_string(),
+        (Some(t), None) if detailed => format!(
+            "{} / {} / {}",
+            format_duration(t.min),
+            format_duration(t.median),
+            format_duration(t.max)
+        ),
+        (Some(t), None) => format_duration(t.median),
+        (None, None) => "-".to_string(),
+    };
+
+    let mut table = String::new();
+    let _ = writeln!(
+        table,
+        "Day  {:<24} {:>w$} {:>w$} {:>w$} {:>10}",
+        "Title",
+        "Parse",
+        "Part 1",
+        "Part 2",
+        "Total",
+        w = width
+    );
+    for run in runs {
+        let part_cell = |part| {
+            let part = run.part(part);
+            cell(
+                part.map(|p| &p.timing),
+                part.and_then(|p| p.answer.as_ref().err()),
+            )
+        };
+        let _ = writeln!(
+            table,
+            "{:>3}  {:<24} {:>w$} {:>w$} {:>w$} {:>10}",
+            run.day,
+            run.title,
+            cell(Some(&run.parse), run.error.as_ref()),
+            part_cell(Part::One),
+            part_cell(Part::Two),
+            format_duration(run.total()),
+            w = width
+        );
+    }
+    let total = runs.iter().map(|r| r.total()).sum();
+    let _ = writeln!(
+        table,
+        "{:<w$} {:>10}",
+        "Total",
+        format_duration(total),
+        w = 5 + 24 + 3 * (width + 1)
+    );
+    table
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+    use std::ops::Not;
+
+    /// Panics in part 1 and never finishes part 2.
+    struct Misbehaving;
+
+    impl Solution for Misbehaving {
+        const DAY: u8 = 25;
+        const TITLE: &'static str = "Misbehaving";
+
+        type Parsed = usize;
+        type Output1 = usize;
+        type Output2 = usize;
+
+        fn parse(input: &Input) -> Result<Self::Parsed> {
+            Ok(input.len())
+        }
+
+        fn part_1(length: &Self::Parsed) -> Result<Self::Output1> {
+            Ok([1, 2, 3][*length])
+        }
+
+        fn part_2(_: &Self::Parsed) -> Result<Self::Output2> {
+            thread::sleep(Duration::from_secs(5));
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn timing_from_samples_test() {
+        // given
+        let samples = [5, 1, 3, 2, 4].map(Duration::from_millis).to_vec();
+
+        // when
+        let timing = Timing::from_samples(samples);
+
+        // then
+        assert_eq!(timing.min, Duration::from_millis(1));
+        assert_eq!(timing.median, Duration::from_millis(3));
+        assert_eq!(timing.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn run_selected_parts_test() {
+        // given
+        let solution = crate::solution(4).unwrap();
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+
+        // when
+        let run = run(solution, &Input::from(input), Parts::Two, 3);
+
+        // then
+        assert_eq!(run.day, 4);
+        assert!(run.part(Part::One).is_none());
+        assert_eq!(run.part(Part::Two).unwrap().answer, Ok(Answer::from(4_u64)));
+    }
+
+    #[test]
+    fn run_invalid_input_test() {
+        // given
+        let solution = crate::solution(4).unwrap();
+        let input = "2-4,6-8\n2-3;4-5";
+
+        // when
+        let run = run(solution, &Input::from(input), Parts::Both, 1);
+
+        // then
+        assert!(run.parts.is_empty());
+        assert_eq!(run.error.unwrap().line, Some(2));
+    }
+
+    #[test]
+    fn run_parallel_keeps_part_order_test() {
+        // given
+        let solution = crate::solution(6).unwrap();
+        let input = Input::from("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let options = Options {
+            repeat: 2,
+            parallel: true,
+            ..Options::default()
+        };
+
+        // when
+        let run = run_with(solution, &input, &options);
+
+        // then
+        let parts: Vec<(Part, &Result<Answer>)> =
+            run.parts.iter().map(|p| (p.part, &p.answer)).collect();
+        assert_eq!(
+            parts,
+            vec![
+                (Part::One, &Ok(Answer::from(7_usize))),
+                (Part::Two, &Ok(Answer::from(19_usize)))
+            ]
+        );
+    }
+
+    #[test]
+    fn panic_and_timeout_test() {
+        // given
+        let input = Input::from("abcdefgh");
+        // long enough for a panic printing its backtrace on a busy machine
+        let options = Options {
+            timeout: Some(Duration::from_secs(1)),
+            ..Options::default()
+        };
+
+        // when
+        let run = run_with(&Misbehaving, &input, &options);
+
+        // then
+        let part_1 = run.part(Part::One).unwrap().answer.as_ref().unwrap_err();
+        let part_2 = run.part(Part::Two).unwrap().answer.as_ref().unwrap_err();
+        assert_eq!(part_1.kind, ErrorKind::Panic);
+        assert_eq!(
+            part_1.reason,
+            "index out of bounds: the len is 3 but the index is 8"
+        );
+        assert_eq!(
+            part_2.to_string(),
+            "day 25: timed out: still running after 1.00s"
+        );
+        assert!(run.is_ok().not());
+        assert!(summary(&[&run]).contains("  panicked  timed out"));
+    }
+
+    #[test]
+    fn panic_without_timeout_test() {
+        // given
+        let input = Input::from("abcd");
+
+        // when
+        let run = run(&Misbehaving, &input, Parts::One, 1);
+
+        // then
+        let error = run.part(Part::One).unwrap().answer.as_ref().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Panic);
+    }
+
+    #[test]
+    fn format_duration_test() {
+        // given
+        let durations = [
+            Duration::from_nanos(999),
+            Duration::from_micros(12),
+            Duration::from_micros(4560),
+            Duration::from_millis(1200),
+        ];
+
+        // when
+        let result: Vec<String> = durations.into_iter().map(format_duration).collect();
+
+        // then
+        assert_eq!(result, vec!["999ns", "12.0µs", "4.56ms", "1.20s"]);
+    }
+}
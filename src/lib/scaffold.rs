@@ -0,0 +1,226 @@
+use std::io;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+use crate::input::Input;
+
+const TEMPLATE: &str = include_str!("../../templates/day.rs.tmpl");
+
+/// Source of a new day module with `example` as the input of its tests.
+pub fn render(day: u8, title: &str, example: &str) -> String {
+    let example = Input::new(example)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    TEMPLATE
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title}}", &title.escape_default().to_string())
+        .replace("{{example}}", &example)
+}
+
+/// `mod_rs` with the module of `day` declared in the order rustfmt keeps, and its solution
+/// registered in the order of days.
+pub fn register(mod_rs: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{}", day);
+    let declaration = format!("pub mod {};", module);
+    let entry = format!("    &day{}::Day{},", day, day);
+    let mut lines: Vec<&str> = mod_rs.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("day {} is already declared", day));
+    }
+
+    let modules = lines
+        .iter()
+        .position(|l| l.starts_with("pub mod "))
+        .ok_or("no module declarations found")?;
+    let at = lines[modules..]
+        .iter()
+        .position(|l| module_name(l).is_none_or(|name| name > module.as_str()))
+        .map_or(lines.len(), |i| modules + i);
+    lines.insert(at, &declaration);
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub static SOLUTIONS"))
+        .ok_or("no SOLUTIONS list found")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| *l == "];")
+            .ok_or("SOLUTIONS list is not closed")?;
+    let at = (start + 1..end)
+        .find(|i| entry_day(lines[*i]).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(at, &entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn module_name(line: &str) -> Option<&str> {
+    line.strip_prefix("pub mod ")?.strip_suffix(';')
+}
+
+fn entry_day(line: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix("&day")?
+        .split_once("::")?
+        .0
+        .parse()
+        .ok()
+}
+
+/// Creates the module of `day` in `src_dir` and registers it in its `mod.rs`, and creates an
+/// empty input in `inputs_dir` unless there is one. Nothing is written when the module exists.
+/// Returns the files written.
+pub fn scaffold(
+    src_dir: &Path,
+    inputs_dir: &Path,
+    day: u8,
+    title: &str,
+    example: &str,
+) -> io::Result<Vec<PathBuf>> {
+    let module = src_dir.join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+    let mod_rs = src_dir.join("mod.rs");
+    let registered = register(&std::fs::read_to_string(&mod_rs)?, day)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    std::fs::write(&module, render(day, title, example))?;
+    std::fs::write(&mod_rs, registered)?;
+    let mut written = vec![module, mod_rs];
+    let input = inputs_dir.join(format!("day{}.txt", day));
+    if input.exists().not() {
+        std::fs::create_dir_all(inputs_dir)?;
+        std::fs::write(&input, "")?;
+        written.push(input);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    const MOD_RS: &str = "use std::any::Any;
+
+pub mod client;
+pub mod day1;
+pub mod day8;
+pub mod error;
+
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day8::Day8,
+];
+";
+
+    #[test]
+    fn register_test() {
+        // given
+        let mod_rs = MOD_RS;
+
+        // when
+        let result = register(mod_rs, 7).unwrap();
+
+        // then
+        assert_eq!(
+            result,
+            "use std::any::Any;
+
+pub mod client;
+pub mod day1;
+pub mod day7;
+pub mod day8;
+pub mod error;
+
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day7::Day7,
+    &day8::Day8,
+];
+"
+        );
+    }
+
+    #[test]
+    fn register_last_day_test() {
+        // given
+        let mod_rs = MOD_RS;
+
+        // when
+        let result = register(mod_rs, 10).unwrap();
+
+        // then
+        assert!(result.contains("pub mod day1;\npub mod day10;\npub mod day8;\n"));
+        assert!(result.contains("    &day8::Day8,\n    &day10::Day10,\n];\n"));
+        assert_eq!(
+            register(&result, 10),
+            Err("day 10 is already declared".to_string())
+        );
+    }
+
+    #[test]
+    fn render_test() {
+        // given
+        let example = "say \"hi\"\r\n\\o/\r\n\r\n";
+
+        // when
+        let result = render(9, "Rope Bridge", example);
+
+        // then
+        assert!(result.contains("pub struct Day9;"));
+        assert!(result.contains("const TITLE: &'static str = \"Rope Bridge\";"));
+        assert!(result.contains("let input = \"say \\\"hi\\\"\n\\\\o/\";"));
+        assert!(result.contains("{{").not());
+    }
+
+    #[test]
+    fn render_escapes_title_test() {
+        // given
+        let title = "Back\\slash \"quoted\"";
+
+        // when
+        let result = render(9, title, "");
+
+        // then
+        assert!(result.contains(r#"const TITLE: &'static str = "Back\\slash \"quoted\"";"#));
+    }
+
+    #[test]
+    fn scaffold_refuses_to_overwrite_test() {
+        // given
+        let dir = temp_dir("scaffold");
+        let (src_dir, inputs_dir) = (dir.join("src"), dir.join("input"));
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::write(src_dir.join("mod.rs"), MOD_RS).unwrap();
+
+        // when
+        let first = scaffold(&src_dir, &inputs_dir, 7, "No Space Left On Device", "$ ls");
+        let mod_rs = std::fs::read_to_string(src_dir.join("mod.rs")).unwrap();
+        let second = scaffold(&src_dir, &inputs_dir, 7, "Other", "");
+
+        // then
+        assert_eq!(
+            first.unwrap(),
+            vec![
+                src_dir.join("day7.rs"),
+                src_dir.join("mod.rs"),
+                inputs_dir.join("day7.txt")
+            ]
+        );
+        assert_eq!(second.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(
+            std::fs::read_to_string(src_dir.join("mod.rs")).unwrap(),
+            mod_rs
+        );
+        assert!(std::fs::read_to_string(src_dir.join("day7.rs"))
+            .unwrap()
+            .contains("No Space Left On Device"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
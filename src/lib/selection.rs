@@ -0,0 +1,190 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Days {
+    All,
+    Single(u8),
+    Range(RangeInclusive<u8>),
+}
+
+impl Days {
+    /// Picks the selected days out of `implemented`. A single day must be implemented, a range
+    /// only needs to contain at least one implemented day.
+    pub fn resolve(&self, implemented: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            Days::All => Ok(implemented.to_vec()),
+            Days::Single(day) if implemented.contains(day) => Ok(vec![*day]),
+            Days::Single(day) => Err(format!("day {} is not implemented yet", day)),
+            Days::Range(range) => {
+                let days: Vec<u8> = implemented
+                    .iter()
+                    .copied()
+                    .filter(|day| range.contains(day))
+                    .collect();
+                if days.is_empty() {
+                    Err(format!(
+                        "no implemented days in {}-{}",
+                        range.start(),
+                        range.end()
+                    ))
+                } else {
+                    Ok(days)
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Days::All);
+        }
+        match s.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("range {} is empty", s));
+                }
+                Ok(Days::Range(start..=end))
+            }
+            None => parse_day(s).map(Days::Single),
+        }
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    str::parse::<u8>(s.trim())
+        .ok()
+        .filter(|day| (FIRST_DAY..=LAST_DAY).contains(day))
+        .ok_or_else(|| {
+            format!(
+                "`{}` is not a day between {} and {}",
+                s, FIRST_DAY, LAST_DAY
+            )
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("`{}` is not a part, expected 1 or 2", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn contains(&self, part: Part) -> bool {
+        matches!(
+            (self, part),
+            (Parts::Both, _) | (Parts::One, Part::One) | (Parts::Two, Part::Two)
+        )
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "both" => Ok(Parts::Both),
+            _ => Err(format!("`{}` is not a part, expected 1, 2 or both", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_days_test() {
+        // given
+        let inputs = ["all", "3", "2-5"];
+
+        // when
+        let result: Vec<Days> = inputs.iter().map(|s| s.parse().unwrap()).collect();
+
+        // then
+        assert_eq!(result, vec![Days::All, Days::Single(3), Days::Range(2..=5)]);
+    }
+
+    #[test]
+    fn parse_invalid_days_test() {
+        // given
+        let inputs = ["0", "26", "5-2", "x", "1-"];
+
+        // when
+        let result: Vec<Result<Days, String>> = inputs.iter().map(|s| s.parse()).collect();
+
+        // then
+        assert!(result.iter().all(|r| r.is_err()));
+    }
+
+    #[test]
+    fn resolve_days_test() {
+        // given
+        let implemented = [1, 2, 3, 4, 5, 6, 8];
+
+        // when
+        let single = Days::Single(7).resolve(&implemented);
+        let range = Days::Range(6..=8).resolve(&implemented);
+
+        // then
+        assert_eq!(single, Err("day 7 is not implemented yet".to_string()));
+        assert_eq!(range, Ok(vec![6, 8]));
+    }
+
+    #[test]
+    fn parts_contains_test() {
+        // given
+        let parts = "2".parse::<Parts>().unwrap();
+
+        // when
+        let result = (parts.contains(Part::One), parts.contains(Part::Two));
+
+        // then
+        assert_eq!(result, (false, true));
+    }
+}
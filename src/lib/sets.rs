@@ -0,0 +1,236 @@
+use std::fmt::Write;
+use std::io;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{Answers, Verification};
+use crate::error::ErrorKind;
+use crate::input::InputSource;
+use crate::runner::{self, DayRun, Options};
+use crate::selection::Part;
+use crate::DynSolution;
+
+/// Name of the set made of the inputs directly in the inputs directory.
+pub const DEFAULT_SET: &str = "default";
+
+/// Name of the set the random inputs are written to by default. It has no answers to check, so
+/// it is not one of the discovered sets.
+pub const GENERATED_SET: &str = "generated";
+
+/// Inputs of one puzzle account: `dayN.txt` files next to their own `answers.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputSet {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl InputSet {
+    pub fn named(inputs_dir: &Path, name: &str) -> Self {
+        InputSet {
+            name: name.to_string(),
+            dir: inputs_dir.join(name),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir.join("answers.toml")
+    }
+}
+
+/// The default set followed by every directory in `inputs_dir` but the generated set, ordered by
+/// name.
+pub fn discover(inputs_dir: &Path) -> io::Result<Vec<InputSet>> {
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(inputs_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_dir() && name != GENERATED_SET {
+            names.push(name);
+        }
+    }
+    names.sort();
+    let default = InputSet {
+        name: DEFAULT_SET.to_string(),
+        dir: inputs_dir.to_path_buf(),
+    };
+    Ok(std::iter::once(default)
+        .chain(names.iter().map(|name| InputSet::named(inputs_dir, name)))
+        .collect())
+}
+
+/// A day run on the input of one set, verified against the answers of that set.
+#[derive(Debug)]
+pub struct SetRun {
+    pub set: String,
+    pub run: DayRun,
+    /// Verification of every part in the same order as the parts, `None` when it failed.
+    pub verifications: Vec<Option<Verification>>,
+}
+
+impl SetRun {
+    /// Whether a part failed, or gave an answer other than the one expected for this set.
+    pub fn is_wrong(&self) -> bool {
+        self.run.is_ok().not()
+            || self
+                .verifications
+                .iter()
+                .any(|v| matches!(v, Some(Verification::Fail { .. })))
+    }
+}
+
+/// Runs `solution` on the input of every set that has one.
+pub fn run_sets(
+    solution: &'static dyn DynSolution,
+    sets: &[InputSet],
+    options: &Options,
+) -> io::Result<Vec<SetRun>> {
+    let day = solution.day();
+    let mut runs = Vec::new();
+    for set in sets {
+        let input = match InputSource::for_day(&set.dir, day).load_for_day(day) {
+            Ok(input) => input,
+            Err(e) if e.kind == ErrorKind::NoInput => continue,
+            Err(e) => {
+                runs.push(SetRun {
+                    set: set.name.clone(),
+                    run: DayRun::failed(solution, e),
+                    verifications: Vec::new(),
+                });
+                continue;
+            }
+        };
+        let answers = Answers::load(&set.answers_path()).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("cannot read {}: {}", set.answers_path().display(), e),
+            )
+        })?;
+        let run = runner::run_with(solution, &input, options);
+        let verifications = run
+            .parts
+            .iter()
+            .map(|part| {
+                let answer = part.answer.as_ref().ok()?;
+                Some(answers.verify(day, part.part, answer))
+            })
+            .collect();
+        runs.push(SetRun {
+            set: set.name.clone(),
+            run,
+            verifications,
+        });
+    }
+    Ok(runs)
+}
+
+/// Table with the answer of every part on every set, next to what was expected for that set.
+pub fn report(runs: &[SetRun]) -> String {
+    let width = runs
+        .iter()
+        .map(|r| r.set.chars().count())
+        .chain(std::iter::once("Set".len()))
+        .max()
+        .unwrap_or_default();
+    let mut table = String::new();
+    let _ = writeln!(table, "{:<width$}  Part 1 / Part 2", "Set");
+    for run in runs {
+        let cells = match &run.run.error {
+            Some(e) => e.to_string(),
+            None => [Part::One, Part::Two]
+                .into_iter()
+                .map(|part| cell(run, part))
+                .collect::<Vec<_>>()
+                .join(" / "),
+        };
+        let _ = writeln!(table, "{:<width$}  {}", run.set, cells);
+    }
+    table
+}
+
+fn cell(run: &SetRun, part: Part) -> String {
+    let Some(index) = run.run.parts.iter().position(|p| p.part == part) else {
+        return "-".to_string();
+    };
+    let answer = match &run.run.parts[index].answer {
+        Ok(answer) => one_line(&answer.to_string()),
+        Err(e) => return e.to_string(),
+    };
+    match run.verifications.get(index).and_then(Option::as_ref) {
+        Some(Verification::Pass) => format!("{} pass", answer),
+        Some(Verification::Fail { expected }) => {
+            format!(
+                "{} FAIL, expected {}",
+                answer,
+                one_line(&expected.to_string())
+            )
+        }
+        Some(Verification::Missing) | None => format!("{} unverified", answer),
+    }
+}
+
+fn one_line(answer: &str) -> String {
+    answer.replace('\n', "|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn discover_test() {
+        // given
+        let dir = temp_dir("sets");
+        for name in ["zoe", "alice", GENERATED_SET] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+        }
+        std::fs::write(dir.join("day1.txt"), "1").unwrap();
+
+        // when
+        let sets = discover(&dir).unwrap();
+
+        // then
+        let names: Vec<&str> = sets.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["default", "alice", "zoe"]);
+        assert_eq!(sets[0].dir, dir);
+        assert_eq!(sets[1].answers_path(), dir.join("alice/answers.toml"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn run_sets_test() {
+        // given
+        let dir = temp_dir("run-sets");
+        let alice = InputSet::named(&dir, "alice");
+        let bob = InputSet::named(&dir, "bob");
+        std::fs::create_dir_all(&alice.dir).unwrap();
+        std::fs::create_dir_all(&bob.dir).unwrap();
+        std::fs::write(dir.join("day6.txt"), "mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        std::fs::write(
+            dir.join("answers.toml"),
+            "[day6]\npart_1 = 7\npart_2 = 19\n",
+        )
+        .unwrap();
+        std::fs::write(alice.dir.join("day6.txt"), "bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap();
+        std::fs::write(alice.answers_path(), "[day6]\npart_1 = 5\npart_2 = 24\n").unwrap();
+        let sets = discover(&dir).unwrap();
+
+        // when
+        let runs = run_sets(crate::solution(6).unwrap(), &sets, &Options::default()).unwrap();
+
+        // then
+        let wrong: Vec<(&str, bool)> = runs
+            .iter()
+            .map(|r| (r.set.as_str(), r.is_wrong()))
+            .collect();
+        assert_eq!(wrong, vec![("default", false), ("alice", true)]);
+        assert_eq!(
+            report(&runs),
+            "Set      Part 1 / Part 2
+default  7 pass / 19 pass
+alice    5 pass / 23 FAIL, expected 24
+"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::{self, Write};
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::client::{Client, ClientError, Outcome};
+use crate::selection::Part;
+
+/// Answer sent to the puzzle website and what it said about it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+/// Every submission so far, stored as JSON Lines in the order they were made.
+#[derive(Debug)]
+pub struct SubmissionLog {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Reads the log at `path`. A file that does not exist yet holds no submissions.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let submissions = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.trim().is_empty().not())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", i + 1, e))
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(SubmissionLog {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    /// The final outcome of `answer` if it was submitted before.
+    pub fn previous(&self, day: u8, part: Part, answer: &Answer) -> Option<&Outcome> {
+        self.submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == part.number() && &s.answer == answer)
+            .map(|s| &s.outcome)
+            .find(|outcome| outcome.is_final())
+    }
+
+    fn append(&mut self, submission: Submission) -> io::Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = serde_json::to_string(&submission).expect("submissions serialize to JSON");
+        writeln!(file, "{}", line)?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Submitted {
+    /// The answer was judged before, the website was not asked again.
+    Logged(Outcome),
+    Sent(Outcome),
+}
+
+/// Submits `answer` unless the log already knows what the website thinks of it.
+pub fn submit(
+    client: &Client,
+    log: &mut SubmissionLog,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+) -> Result<Submitted, ClientError> {
+    if let Some(outcome) = log.previous(day, part, answer) {
+        return Ok(Submitted::Logged(outcome.clone()));
+    }
+    let outcome = client.submit_answer(day, part, answer)?;
+    log.append(Submission {
+        day,
+        part: part.number(),
+        answer: answer.clone(),
+        outcome: outcome.clone(),
+    })?;
+    Ok(Submitted::Sent(outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::mock_server;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn wrong_answer_is_submitted_once_test() {
+        // given
+        let (base_url, server) = mock_server(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too low.</p>",
+            ),
+            (200, "<p>That's the right answer!</p>"),
+        ]);
+        let client = Client::new(&base_url, "secret".to_string());
+        let dir = temp_dir("submit");
+        let path = dir.join("submissions.jsonl");
+        let mut log = SubmissionLog::load(&path).unwrap();
+
+        // when
+        let first = submit(&client, &mut log, 1, Part::One, &Answer::from(10_u64)).unwrap();
+        let mut log = SubmissionLog::load(&path).unwrap();
+        let again = submit(&client, &mut log, 1, Part::One, &Answer::from(10_u64)).unwrap();
+        let other = submit(&client, &mut log, 1, Part::One, &Answer::from(12_u64)).unwrap();
+
+        // then
+        assert_eq!(first, Submitted::Sent(Outcome::TooLow));
+        assert_eq!(again, Submitted::Logged(Outcome::TooLow));
+        assert_eq!(other, Submitted::Sent(Outcome::Correct));
+        assert_eq!(server.join().unwrap().len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"day\":1,\"part\":1,\"answer\":10,\"outcome\":\"too_low\"}\n\
+             {\"day\":1,\"part\":1,\"answer\":12,\"outcome\":\"correct\"}\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rate_limited_answer_is_submitted_again_test() {
+        // given
+        let mut log = SubmissionLog {
+            path: PathBuf::from("unused.jsonl"),
+            submissions: vec![Submission {
+                day: 2,
+                part: 2,
+                answer: Answer::from(5_u64),
+                outcome: Outcome::RateLimited { wait_s: Some(30) },
+            }],
+        };
+
+        // when
+        let previous = log.previous(2, Part::Two, &Answer::from(5_u64)).cloned();
+        log.submissions[0].outcome = Outcome::Wrong;
+        let judged = log.previous(2, Part::Two, &Answer::from(5_u64)).cloned();
+
+        // then
+        assert_eq!(previous, None);
+        assert_eq!(judged, Some(Outcome::Wrong));
+    }
+}
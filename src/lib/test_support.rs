@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+/// Empty directory of this test process, named after `name` so tests running at the same time
+/// do not share it.
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
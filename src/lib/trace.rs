@@ -0,0 +1,61 @@
+//! Optional instrumentation of the solvers. Without the `trace` feature the events compile to
+//! nothing, so they cost nothing either.
+
+#[cfg(feature = "trace")]
+use std::fs::File;
+#[cfg(feature = "trace")]
+use std::io;
+#[cfg(feature = "trace")]
+use std::path::Path;
+#[cfg(feature = "trace")]
+use std::sync::Mutex;
+
+/// Records a `tracing` event at `$level` with the `trace` feature, and nothing without it. The
+/// fields are only evaluated when the feature is on.
+macro_rules! event {
+    ($level:ident, $($field:tt)+) => {
+        #[cfg(feature = "trace")]
+        tracing::$level!($($field)+);
+    };
+}
+
+/// Records an event at `$level` for every item of `$iter`, an iterator or option, with the
+/// `trace` feature. Without it the macro is just `$iter`, so instrumenting a chain leaves the
+/// chain as it was.
+#[cfg(feature = "trace")]
+macro_rules! inspect {
+    ($iter:expr, |$item:pat_param| $level:ident, $($field:tt)+) => {
+        $iter.inspect(|$item| tracing::$level!($($field)+))
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! inspect {
+    ($iter:expr, |$item:pat_param| $level:ident, $($field:tt)+) => {
+        $iter
+    };
+}
+
+pub(crate) use {event, inspect};
+
+/// Prints the events of the solvers to `file`, or to the standard error, from `debug` up, or
+/// every event with a `verbosity` above 1.
+#[cfg(feature = "trace")]
+pub fn init(verbosity: u8, file: Option<&Path>) -> io::Result<()> {
+    let level = if verbosity > 1 {
+        tracing::Level::TRACE
+    } else {
+        tracing::Level::DEBUG
+    };
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_target(false);
+    match file {
+        Some(path) => subscriber
+            .with_ansi(false)
+            .with_writer(Mutex::new(File::create(path)?))
+            .init(),
+        None => subscriber.with_writer(io::stderr).init(),
+    }
+    Ok(())
+}
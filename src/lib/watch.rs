@@ -0,0 +1,234 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::runner::DayRun;
+use crate::selection::Part;
+
+/// What polling can tell about a file without reading it, `None` when it does not exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp(Option<(SystemTime, u64)>);
+
+impl Stamp {
+    fn of(path: &Path) -> Self {
+        Stamp(
+            std::fs::metadata(path)
+                .ok()
+                .and_then(|m| Some((m.modified().ok()?, m.len()))),
+        )
+    }
+}
+
+/// Notices changes of files by polling their modification time and length.
+#[derive(Debug)]
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let stamp = Stamp::of(&path);
+                (path, stamp)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    /// Whether any file was created, changed or removed since the previous poll.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        for (path, stamp) in &mut self.files {
+            let current = Stamp::of(path);
+            if current != *stamp {
+                *stamp = current;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Answers of `current` next to the ones of `previous`, marking the parts whose answer changed.
+/// Multiline answers are put next to each other line by line.
+pub fn side_by_side(previous: Option<&DayRun>, current: &DayRun) -> String {
+    let cells: Vec<(Part, String, String)> = [Part::One, Part::Two]
+        .into_iter()
+        .map(|part| {
+            let before = previous.map_or("-".to_string(), |run| cell(run, part));
+            (part, before, cell(current, part))
+        })
+        .collect();
+    let width = cells
+        .iter()
+        .flat_map(|(_, before, _)| before.lines())
+        .map(|l| l.chars().count())
+        .chain(std::iter::once("Previous".len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut table = String::new();
+    let _ = writeln!(table, "{:<8}{:<width$}  New", "", "Previous");
+    for (part, before, after) in cells {
+        let changed = previous.is_some() && before != after;
+        let (before, after): (Vec<&str>, Vec<&str>) =
+            (before.lines().collect(), after.lines().collect());
+        for i in 0..before.len().max(after.len()) {
+            let label = if i == 0 {
+                format!("Part {}", part)
+            } else {
+                String::new()
+            };
+            let left = before.get(i).copied().unwrap_or_default();
+            let right = after.get(i).copied().unwrap_or_default();
+            let mark = if i == 0 && changed { "  (changed)" } else { "" };
+            let line = format!("{:<8}{:<width$}  {}{}", label, left, right, mark);
+            let _ = writeln!(table, "{}", line.trim_end());
+        }
+    }
+    table
+}
+
+fn cell(run: &DayRun, part: Part) -> String {
+    match (run.part(part), &run.error) {
+        (Some(part), _) => match &part.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error: {}", e.reason),
+        },
+        (None, Some(e)) if run.has_no_input() => format!("no input: {}", e.reason),
+        (None, Some(e)) => format!("error: {}", e.reason),
+        (None, None) => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::input::Input;
+    use crate::runner::{run, PartRun, Timing};
+    use crate::selection::Parts;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn poll_test() {
+        // given
+        let dir = temp_dir("watch");
+        let (input, example) = (dir.join("day6.txt"), dir.join("example.txt"));
+        std::fs::write(&input, "abcd").unwrap();
+        let mut watcher = Watcher::new([input.clone(), example.clone()]);
+
+        // when
+        let unchanged = watcher.poll();
+        std::fs::write(&example, "abcde").unwrap();
+        let created = watcher.poll();
+        std::fs::write(&input, "abcdefg").unwrap();
+        let changed = watcher.poll();
+        std::fs::remove_file(&input).unwrap();
+        let removed = watcher.poll();
+        let settled = watcher.poll();
+
+        // then
+        assert_eq!(
+            (unchanged, created, changed, removed, settled),
+            (false, true, true, true, false)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn side_by_side_test() {
+        // given
+        let solution = crate::solution(6).unwrap();
+        let before = run(
+            solution,
+            &Input::from("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+            Parts::Both,
+            1,
+        );
+        let after = run(
+            solution,
+            &Input::from("nppdvjthqldpwncqszvftbrmjlhg"),
+            Parts::Both,
+            1,
+        );
+
+        // when
+        let first = side_by_side(None, &before);
+        let second = side_by_side(Some(&before), &after);
+
+        // then
+        assert_eq!(
+            first,
+            "        Previous  New
+Part 1  -         5
+Part 2  -         23
+"
+        );
+        assert_eq!(
+            second,
+            "        Previous  New
+Part 1  5         6  (changed)
+Part 2  23        23
+"
+        );
+    }
+
+    #[test]
+    fn side_by_side_failed_test() {
+        // given
+        let solution = crate::solution(5).unwrap();
+        let before = run(
+            solution,
+            &Input::from("[A]\n 1\n\nmove 1 from 1 to 1"),
+            Parts::One,
+            1,
+        );
+        let after = DayRun::failed(solution, crate::error::Error::new(5, "no stacks"));
+
+        // when
+        let result = side_by_side(Some(&before), &after);
+
+        // then
+        assert_eq!(
+            result,
+            "        Previous  New
+Part 1  A         error: no stacks  (changed)
+Part 2  -         error: no stacks  (changed)
+"
+        );
+    }
+
+    #[test]
+    fn side_by_side_block_test() {
+        // given
+        let block = |answer: &str| DayRun {
+            day: 10,
+            title: "Cathode-Ray Tube",
+            parse: Timing::default(),
+            parts: vec![PartRun {
+                part: Part::Two,
+                answer: Ok(Answer::from(answer)),
+                timing: Timing::default(),
+            }],
+            error: None,
+        };
+        let (before, after) = (block("#..#\n.##."), block("####\n#..#\n####"));
+
+        // when
+        let result = side_by_side(Some(&before), &after);
+
+        // then
+        assert_eq!(
+            result,
+            "        Previous  New
+Part 1  -         -
+Part 2  #..#      ####  (changed)
+        .##.      #..#
+                  ####
+"
+        );
+    }
+}
@@ -1,33 +1,943 @@
-macro_rules! day {
-    ($path_part:ident) => {
-        let day = stringify!($path_part);
-        let input = read_file(&format!("input/{}.txt", day));
-        println!(
-            "===== Day {} =====",
-            day.chars().filter(|c| c.is_digit(10)).collect::<String>()
+use std::ops::Not;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::answer::Answer;
+use advent_of_code_2022::answers::{Answers, Verification};
+use advent_of_code_2022::client::{self, Client, Fetched, Outcome, DEFAULT_BASE_URL};
+use advent_of_code_2022::fuzz;
+use advent_of_code_2022::generate;
+use advent_of_code_2022::history::{self, HistoryEntry, Mode};
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::output::{DayRecord, Format, RunRecord};
+use advent_of_code_2022::report::{self, ReportFormat, ReportRow};
+use advent_of_code_2022::runner::{self, format_duration, DayRun};
+use advent_of_code_2022::scaffold;
+use advent_of_code_2022::selection::{Days, Part, Parts, FIRST_DAY, LAST_DAY};
+use advent_of_code_2022::sets;
+use advent_of_code_2022::submissions::{self, SubmissionLog, Submitted};
+use advent_of_code_2022::watch::{self, Watcher};
+use advent_of_code_2022::{DynSolution, SOLUTIONS};
+use clap::{Args, Parser, Subcommand};
+use rayon::prelude::*;
+
+/// Runs the Advent of Code 2022 solutions.
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solutions, the default when no command is given
+    Run(RunArgs),
+    /// Download puzzle inputs that are not in the inputs directory yet
+    Fetch(FetchArgs),
+    /// Submit an answer, never sending one that was already judged
+    Submit(SubmitArgs),
+    /// Generate the module of a new day and register it
+    New(NewArgs),
+    /// Run a day again whenever its input or example changes
+    Watch(WatchArgs),
+    /// Write a page with the answers, verification, time and size of every day
+    Report(ReportArgs),
+    /// Compare the times of the latest run with an earlier one run the same way
+    PerfDiff(PerfDiffArgs),
+    /// Write random inputs for stress and scale testing as a set of their own
+    Generate(GenerateArgs),
+    /// Run the days on mangled inputs and random bytes, saving every input that panics or hangs
+    Fuzz(FuzzArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run: a number, a range such as `3-5`, or `all`
+    #[arg(default_value = "all")]
+    days: Days,
+
+    /// Part to run: `1`, `2` or `both`
+    #[arg(short, long, default_value = "both")]
+    part: Parts,
+
+    /// Run every step this many times and report min, median and max
+    #[arg(short, long, default_value_t = 1)]
+    repeat: usize,
+
+    /// Compare every answer with the answers file, fail on any mismatch; needs --answers with
+    /// --input
+    #[arg(long, conflicts_with = "record")]
+    verify: bool,
+
+    /// Store every answer in the answers file
+    #[arg(long, conflicts_with = "input")]
+    record: bool,
+
+    /// File with the expected answers [default: <INPUTS_DIR>/answers.toml]
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Read the input from this file instead, `-` reads the standard input; needs a single day
+    #[arg(short, long)]
+    input: Option<InputSource>,
+
+    /// Directory with the `dayN.txt` inputs
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = "input")]
+    inputs_dir: PathBuf,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Run the days, and both parts of every day, at the same time; output stays in day order
+    #[arg(long)]
+    parallel: bool,
+
+    /// Threads to run on with --parallel [default: one per CPU]
+    #[arg(short, long, requires = "parallel")]
+    jobs: Option<usize>,
+
+    /// Use the inputs and answers of this set, a directory in the inputs directory
+    #[arg(long)]
+    set: Option<String>,
+
+    /// Run every day on the inputs of every set, reporting answers other than the set expects
+    #[arg(long, conflicts_with_all = ["set", "input", "record", "answers", "format"])]
+    all_sets: bool,
+
+    /// File the median times of every run without -v are appended to, with the commit they ran on
+    #[arg(long, default_value = "perf-history.jsonl")]
+    history: PathBuf,
+
+    /// Do not append the times of this run to the history
+    #[arg(long)]
+    no_history: bool,
+
+    /// Give up on a step of a day after this many seconds, e.g. `0.5`, and go on with the next
+    #[arg(short, long, value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Print what happens inside the solvers, `-vv` for every detail; needs the `trace` feature
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Write the trace to this file instead of the standard error
+    #[arg(long, requires = "verbose")]
+    trace_file: Option<PathBuf>,
+}
+
+impl RunArgs {
+    fn options(&self) -> runner::Options {
+        runner::Options {
+            parts: self.part,
+            repeat: self.repeat,
+            parallel: self.parallel,
+            timeout: self.timeout,
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+fn start_tracing(cli: &RunArgs) -> Result<(), String> {
+    advent_of_code_2022::trace::init(cli.verbose, cli.trace_file.as_deref())
+        .map_err(|e| format!("cannot write the trace: {}", e))
+}
+
+#[cfg(not(feature = "trace"))]
+fn start_tracing(_: &RunArgs) -> Result<(), String> {
+    Err("built without tracing, rebuild with `--features trace` to see the trace".to_string())
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    str::parse::<f64>(s)
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|timeout| timeout.is_zero().not())
+        .ok_or_else(|| format!("`{}` is not a number of seconds above 0", s))
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    /// Day to download: a number, a range such as `3-5`, or `all`
+    days: Days,
+
+    #[command(flatten)]
+    server: ServerArgs,
+
+    /// Directory to save the `dayN.txt` inputs in
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = "input")]
+    inputs_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    /// Day of the answer
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    day: u8,
+
+    /// Part of the answer: `1` or `2`
+    part: Part,
+
+    /// Answer to submit [default: the answer the solution gives for the day's input]
+    answer: Option<Answer>,
+
+    #[command(flatten)]
+    server: ServerArgs,
+
+    /// Directory with the `dayN.txt` inputs
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = "input")]
+    inputs_dir: PathBuf,
+
+    /// File logging every submission [default: <INPUTS_DIR>/submissions.jsonl]
+    #[arg(long)]
+    log: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Day to generate
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    day: u8,
+
+    /// Title of the puzzle [default: Day <DAY>]
+    #[arg(long)]
+    title: Option<String>,
+
+    /// File with the example input used by the tests, `-` reads the standard input
+    #[arg(long)]
+    example: Option<InputSource>,
+
+    /// Directory with the day modules and their `mod.rs`
+    #[arg(long, default_value = "src/lib")]
+    src_dir: PathBuf,
+
+    /// Directory with the `dayN.txt` inputs
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = "input")]
+    inputs_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// Day to run
+    day: u8,
+
+    /// Example input to run next to the day's input
+    #[arg(long)]
+    example: Option<PathBuf>,
+
+    /// Milliseconds between two checks of the files
+    #[arg(long, default_value_t = 500)]
+    interval: u64,
+
+    /// Directory with the `dayN.txt` inputs
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = "input")]
+    inputs_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct ReportArgs {
+    /// Format of the page
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Markdown)]
+    format: ReportFormat,
+
+    /// File to write, `-` writes to the standard output [default: REPORT.md or report.html]
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Run every step this many times and report the median time
+    #[arg(short, long, default_value_t = 1)]
+    repeat: usize,
+
+    /// File with the expected answers [default: <INPUTS_DIR>/answers.toml]
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Directory with the `dayN.txt` inputs
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = "input")]
+    inputs_dir: PathBuf,
+
+    /// Directory with the day modules, to count their lines
+    #[arg(long, default_value = "src/lib")]
+    src_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct PerfDiffArgs {
+    /// Commit, or the start of its hash, to compare with [default: the run before the latest]
+    #[arg(long)]
+    baseline: Option<String>,
+
+    /// Flag steps that got slower by more than this many percent
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// File with the history of runs
+    #[arg(long, default_value = "perf-history.jsonl")]
+    history: PathBuf,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day to generate an input for: a number, a range such as `3-5`, or `all`
+    #[arg(default_value = "all")]
+    days: Days,
+
+    /// Seed of the random numbers, the same seed gives the same inputs
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// How large the inputs are, e.g. the number of elves or the side of the tree grid
+    #[arg(long, default_value_t = 1000)]
+    size: usize,
+
+    /// Input set to write the inputs to, run them with `--set`
+    #[arg(long, default_value = sets::GENERATED_SET)]
+    set: String,
+
+    /// Directory with the input sets
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = "input")]
+    inputs_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct FuzzArgs {
+    /// Day to fuzz: a number, a range such as `3-5`, or `all`
+    #[arg(default_value = "all")]
+    days: Days,
+
+    /// Inputs to try on every day
+    #[arg(long, default_value_t = 10_000)]
+    cases: u64,
+
+    /// Number of the first case, every case is the same on every run
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Count a step that runs longer than this many seconds as hanging
+    #[arg(short, long, value_parser = parse_timeout, default_value = "5")]
+    timeout: Duration,
+
+    /// Directory to save the inputs that crash in
+    #[arg(long, default_value = "fuzz-crashes")]
+    crashes_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct ServerArgs {
+    /// Puzzle website, anything serving the same paths works
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// File with the session token, used when `AOC_SESSION` is not set
+    #[arg(long, default_value = ".aoc-session")]
+    session_file: PathBuf,
+}
+
+impl ServerArgs {
+    fn client(&self) -> Result<Client, String> {
+        let session = client::session(&self.session_file).map_err(|e| e.to_string())?;
+        Ok(Client::new(&self.base_url, session))
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Run(args)) => run(args),
+        Some(Command::Fetch(args)) => fetch(args),
+        Some(Command::Submit(args)) => submit(args),
+        Some(Command::New(args)) => new(args),
+        Some(Command::Watch(args)) => watch(args),
+        Some(Command::Report(args)) => write_report(args),
+        Some(Command::PerfDiff(args)) => perf_diff(args),
+        Some(Command::Generate(args)) => write_generated(args),
+        Some(Command::Fuzz(args)) => run_fuzz(args),
+        None => run(cli.run),
+    }
+}
+
+fn fetch(args: FetchArgs) -> ExitCode {
+    let all: Vec<u8> = (FIRST_DAY..=LAST_DAY).collect();
+    let selected = match args.days.resolve(&all) {
+        Ok(selected) => selected,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = match args.server.client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut errors = 0;
+    for day in selected {
+        match client::fetch(&client, day, &args.inputs_dir) {
+            Ok(Fetched::Downloaded(path)) => println!("Day {}: downloaded {}", day, path.display()),
+            Ok(Fetched::Cached(path)) => println!("Day {}: cached in {}", day, path.display()),
+            Err(e) => {
+                eprintln!("Day {}: error: {}", day, e);
+                errors += 1;
+            }
+        }
+    }
+    if errors > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn submit(args: SubmitArgs) -> ExitCode {
+    let answer = match args.answer.clone().map_or_else(|| solve(&args), Ok) {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = match args.server.client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let log_path = args
+        .log
+        .clone()
+        .unwrap_or_else(|| args.inputs_dir.join("submissions.jsonl"));
+    let mut log = match SubmissionLog::load(&log_path) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {}", log_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let outcome = match submissions::submit(&client, &mut log, args.day, args.part, &answer) {
+        Ok(Submitted::Sent(outcome)) => {
+            println!(
+                "Day {} part {}: {}: {}",
+                args.day, args.part, answer, outcome
+            );
+            outcome
+        }
+        Ok(Submitted::Logged(outcome)) => {
+            println!(
+                "Day {} part {}: {} was already submitted, not sending it again: {}",
+                args.day, args.part, answer, outcome
+            );
+            outcome
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match outcome {
+        Outcome::Correct | Outcome::AlreadySolved => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Answer the solution of the day gives for its default input.
+fn solve(args: &SubmitArgs) -> Result<Answer, String> {
+    let solution = advent_of_code_2022::solution(args.day)
+        .ok_or_else(|| format!("day {} is not implemented yet", args.day))?;
+    let input = InputSource::for_day(&args.inputs_dir, args.day)
+        .load_for_day(args.day)
+        .map_err(|e| e.to_string())?;
+    let parsed = solution.parse_input(&input).map_err(|e| e.to_string())?;
+    solution
+        .solve(parsed.as_ref(), args.part)
+        .map_err(|e| e.to_string())
+}
+
+fn new(args: NewArgs) -> ExitCode {
+    let example = match &args.example {
+        Some(source) => match source.load() {
+            Ok(example) => example.to_string(),
+            Err(e) => {
+                eprintln!("error: cannot read {}: {}", source, e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => String::new(),
+    };
+    let title = args
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", args.day));
+    match scaffold::scaffold(&args.src_dir, &args.inputs_dir, args.day, &title, &example) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn watch(args: WatchArgs) -> ExitCode {
+    let Some(solution) = advent_of_code_2022::solution(args.day) else {
+        eprintln!("error: day {} is not implemented yet", args.day);
+        return ExitCode::FAILURE;
+    };
+    let mut sources = vec![InputSource::for_day(&args.inputs_dir, args.day)];
+    sources.extend(args.example.iter().cloned().map(InputSource::File));
+    let mut watcher = Watcher::new(sources.iter().filter_map(|source| match source {
+        InputSource::File(path) => Some(path.clone()),
+        InputSource::Stdin => None,
+    }));
+    let mut previous: Vec<Option<DayRun>> = sources.iter().map(|_| None).collect();
+    loop {
+        for (source, previous) in sources.iter().zip(&mut previous) {
+            let run = match source.load_for_day(args.day) {
+                Ok(input) => runner::run(solution, &input, Parts::Both, 1),
+                Err(e) => DayRun::failed(solution, e),
+            };
+            print_header(&run, source);
+            print!("{}", watch::side_by_side(previous.as_ref(), &run));
+            *previous = Some(run);
+        }
+        println!("\nWaiting for changes, press Ctrl-C to stop");
+        while watcher.poll().not() {
+            std::thread::sleep(Duration::from_millis(args.interval));
+        }
+        println!();
+    }
+}
+
+fn write_report(args: ReportArgs) -> ExitCode {
+    let answers_path = args
+        .answers
+        .clone()
+        .unwrap_or_else(|| args.inputs_dir.join("answers.toml"));
+    let answers = match Answers::load(&answers_path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {}", answers_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let rows: Vec<ReportRow> = (FIRST_DAY..=LAST_DAY)
+        .map(|day| {
+            let lines = std::fs::read_to_string(args.src_dir.join(format!("day{}.rs", day)))
+                .ok()
+                .map(|source| report::lines_of_code(&source));
+            let run =
+                advent_of_code_2022::solution(day).map(|solution| {
+                    match InputSource::for_day(&args.inputs_dir, day).load_for_day(day) {
+                        Ok(input) => runner::run(solution, &input, Parts::Both, args.repeat),
+                        Err(e) => DayRun::failed(solution, e),
+                    }
+                });
+            let verifications = run
+                .iter()
+                .flat_map(|run| &run.parts)
+                .map(|part| Some(answers.verify(day, part.part, part.answer.as_ref().ok()?)))
+                .collect();
+            ReportRow {
+                day,
+                run,
+                verifications,
+                lines,
+            }
+        })
+        .collect();
+
+    let page = report::render(&rows, args.format);
+    let output = args.output.unwrap_or_else(|| {
+        PathBuf::from(match args.format {
+            ReportFormat::Markdown => "REPORT.md",
+            ReportFormat::Html => "report.html",
+        })
+    });
+    if output.as_os_str() == "-" {
+        print!("{}", page);
+    } else if let Err(e) = std::fs::write(&output, page) {
+        eprintln!("error: cannot write {}: {}", output.display(), e);
+        return ExitCode::FAILURE;
+    } else {
+        eprintln!("Report written to {}", output.display());
+    }
+    ExitCode::SUCCESS
+}
+
+fn perf_diff(args: PerfDiffArgs) -> ExitCode {
+    let entries = match history::load(&args.history) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {}", args.history.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some((latest, earlier)) = entries.split_last() else {
+        eprintln!("error: {} has no runs yet", args.history.display());
+        return ExitCode::FAILURE;
+    };
+    let baseline = history::baseline(earlier, latest, args.baseline.as_deref());
+    let Some(baseline) = baseline else {
+        eprintln!(
+            "error: no run {}before the latest one with the same profile, inputs, --parallel, --repeat and --timeout",
+            args.baseline
+                .as_ref()
+                .map(|c| format!("of {} ", c))
+                .unwrap_or_default()
         );
-        println!(
-            "Part 1: {}",
-            advent_of_code_2022::$path_part::part_1(&input)
+        return ExitCode::FAILURE;
+    };
+
+    println!("Baseline {}, latest {}\n", baseline.commit, latest.commit);
+    let changes = history::compare(baseline, latest);
+    print!("{}", history::diff_table(&changes, args.threshold));
+    let slower = changes
+        .iter()
+        .filter(|c| c.is_regression(args.threshold))
+        .count();
+    if slower > 0 {
+        eprintln!(
+            "\n{} steps got more than {}% slower",
+            slower, args.threshold
         );
-        println!(
-            "Part 2: {}",
-            advent_of_code_2022::$path_part::part_2(&input)
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn write_generated(args: GenerateArgs) -> ExitCode {
+    let selected = match args.days.resolve(&generate::days()) {
+        Ok(selected) => selected,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let dir = sets::InputSet::named(&args.inputs_dir, &args.set).dir;
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("error: cannot create {}: {}", dir.display(), e);
+        return ExitCode::FAILURE;
+    }
+    for day in selected {
+        let input = generate::generate(day, args.seed, args.size).expect("day has a generator");
+        let path = dir.join(format!("day{}.txt", day));
+        if let Err(e) = std::fs::write(&path, input) {
+            eprintln!("error: cannot write {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+        println!("Day {}: wrote {}", day, path.display());
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_fuzz(args: FuzzArgs) -> ExitCode {
+    let implemented: Vec<u8> = SOLUTIONS.iter().map(|s| s.day()).collect();
+    let selected = match args.days.resolve(&implemented) {
+        Ok(selected) => selected,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let options = runner::Options {
+        timeout: Some(args.timeout),
+        ..runner::Options::default()
+    };
+    // the panics are reported as crashes, not printed as they happen
+    fuzz::silence_day_panics();
+    let mut crashes = Vec::new();
+    for day in selected {
+        let solution = advent_of_code_2022::solution(day).expect("day is implemented");
+        let found = fuzz::fuzz(
+            solution,
+            args.seed..args.seed.saturating_add(args.cases),
+            &options,
         );
+        println!("Day {}: {} cases, {} crashes", day, args.cases, found.len());
+        crashes.extend(found);
+    }
+
+    if crashes.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+    if let Err(e) = std::fs::create_dir_all(&args.crashes_dir) {
+        eprintln!("error: cannot create {}: {}", args.crashes_dir.display(), e);
+        return ExitCode::FAILURE;
+    }
+    for crash in crashes {
+        let path = args
+            .crashes_dir
+            .join(format!("day{}-case{}.txt", crash.day, crash.case));
+        match std::fs::write(&path, &crash.input) {
+            Ok(()) => eprintln!("{}, input saved to {}", crash.error, path.display()),
+            Err(e) => eprintln!("{}, cannot save {}: {}", crash.error, path.display(), e),
+        }
+    }
+    ExitCode::FAILURE
+}
+
+fn run(mut cli: RunArgs) -> ExitCode {
+    let implemented: Vec<u8> = SOLUTIONS.iter().map(|s| s.day()).collect();
+    let selected = match cli.days.resolve(&implemented) {
+        Ok(selected) => selected,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if cli.verbose > 0 {
+        if let Err(e) = start_tracing(&cli) {
+            eprintln!("warning: {}", e);
+        }
+    }
+    if cli.all_sets {
+        return run_all_sets(&cli, &selected);
+    }
+    if let Some(set) = &cli.set {
+        cli.inputs_dir = cli.inputs_dir.join(set);
+    }
+    if cli.input.is_some() && selected.len() != 1 {
+        eprintln!(
+            "error: --input needs a single day, {} selected",
+            selected.len()
+        );
+        return ExitCode::FAILURE;
+    }
+    if cli.verify && cli.input.is_some() && cli.answers.is_none() {
+        eprintln!("error: --verify with --input needs the --answers of that input");
+        return ExitCode::FAILURE;
+    }
+    let answers_path = cli
+        .answers
+        .clone()
+        .unwrap_or_else(|| cli.inputs_dir.join("answers.toml"));
+    let mut answers = match Answers::load(&answers_path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {}", answers_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let solutions: Vec<&'static dyn DynSolution> = SOLUTIONS
+        .iter()
+        .copied()
+        .filter(|s| selected.contains(&s.day()))
+        .collect();
+    let options = cli.options();
+    let execute = |solution: &'static dyn DynSolution| {
+        let source = cli
+            .input
+            .clone()
+            .unwrap_or_else(|| InputSource::for_day(&cli.inputs_dir, solution.day()));
+        let run = match source.load_for_day(solution.day()) {
+            Ok(input) => runner::run_with(solution, &input, &options),
+            Err(e) => DayRun::failed(solution, e),
+        };
+        (run, source)
+    };
+    let pool = match cli
+        .parallel
+        .then(|| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(cli.jobs.unwrap_or(0))
+                .build()
+        })
+        .transpose()
+    {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("error: cannot start threads: {}", e);
+            return ExitCode::FAILURE;
+        }
     };
+    let start = Instant::now();
+    let runs: Box<dyn Iterator<Item = (DayRun, InputSource)>> = match &pool {
+        Some(pool) => {
+            let runs: Vec<_> = pool.install(|| solutions.par_iter().map(|s| execute(*s)).collect());
+            Box::new(runs.into_iter())
+        }
+        None => Box::new(solutions.iter().map(|s| execute(*s))),
+    };
+
+    let mut days = Vec::new();
+    for (run, source) in runs {
+        if cli.format == Format::Text {
+            print_header(&run, &source);
+        }
+        let verifications: Vec<Option<Verification>> = run
+            .parts
+            .iter()
+            .map(|part| {
+                let answer = part.answer.as_ref().ok()?;
+                if cli.record {
+                    answers.set(run.day, part.part, answer.clone());
+                }
+                cli.verify
+                    .then(|| answers.verify(run.day, part.part, answer))
+            })
+            .collect();
+        match cli.format {
+            Format::Text => print_day(&run, &verifications),
+            Format::Jsonl => println!(
+                "{}",
+                serde_json::to_string(&DayRecord::new(&run, &source, &verifications))
+                    .expect("records serialize to JSON")
+            ),
+            Format::Json => {}
+        }
+        days.push((run, source, verifications));
+    }
+    let wall = start.elapsed();
+
+    let verifications = days.iter().flat_map(|(_, _, v)| v.iter().flatten());
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for verification in verifications {
+        match verification {
+            Verification::Pass => passed += 1,
+            Verification::Fail { .. } => failed += 1,
+            Verification::Missing => missing += 1,
+        }
+    }
+    match cli.format {
+        Format::Text => {
+            let runs: Vec<&DayRun> = days.iter().map(|(run, _, _)| run).collect();
+            println!();
+            print!("{}", runner::summary(&runs));
+            println!(
+                "Wall time {}, CPU time {} summed over all steps",
+                format_duration(wall),
+                format_duration(runs.iter().map(|r| r.total()).sum())
+            );
+            if cli.verify {
+                println!(
+                    "\nVerification: {} passed, {} failed, {} missing",
+                    passed, failed, missing
+                );
+            }
+        }
+        Format::Json => {
+            let records = days
+                .iter()
+                .map(|(run, source, verifications)| DayRecord::new(run, source, verifications))
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&RunRecord::new(records, wall))
+                    .expect("records serialize to JSON")
+            );
+        }
+        Format::Jsonl => {}
+    }
+
+    // tracing slows the days down too much for their times to compare
+    if cli.input.is_none() && cli.no_history.not() && cli.verbose == 0 {
+        let runs: Vec<&DayRun> = days.iter().map(|(run, _, _)| run).collect();
+        let mode = Mode {
+            profile: Mode::profile().to_string(),
+            inputs_dir: cli.inputs_dir.clone(),
+            set: cli.set.clone(),
+            parallel: cli.parallel,
+            repeat: cli.repeat,
+            timeout_ns: cli.timeout.map(|t| t.as_nanos() as u64),
+        };
+        let entry = HistoryEntry::new(history::current_commit(), mode, &runs);
+        if let Err(e) = history::append(&cli.history, &entry) {
+            eprintln!("warning: cannot write {}: {}", cli.history.display(), e);
+        }
+    }
+    if cli.record {
+        if let Err(e) = answers.save(&answers_path) {
+            eprintln!("error: cannot write {}: {}", answers_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+        eprintln!("Answers recorded in {}", answers_path.display());
+    }
+    let no_input = days.iter().filter(|(run, _, _)| run.has_no_input()).count();
+    if no_input > 0 {
+        eprintln!("{} of {} days had no input", no_input, days.len());
+    }
+    let errors = days.iter().filter(|(run, _, _)| run.is_ok().not()).count();
+    if errors > 0 {
+        eprintln!("{} of {} days failed", errors, days.len());
+    }
+    if failed > 0 || errors > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_all_sets(cli: &RunArgs, selected: &[u8]) -> ExitCode {
+    let sets = match sets::discover(&cli.inputs_dir) {
+        Ok(sets) => sets,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {}", cli.inputs_dir.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let options = cli.options();
+    let mut wrong = Vec::new();
+    for solution in SOLUTIONS.iter().filter(|s| selected.contains(&s.day())) {
+        let runs = match sets::run_sets(*solution, &sets, &options) {
+            Ok(runs) => runs,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        println!("===== Day {}: {} =====", solution.day(), solution.title());
+        print!("{}", sets::report(&runs));
+        wrong.extend(
+            runs.iter()
+                .filter(|r| r.is_wrong())
+                .map(|r| format!("day {} on {}", r.run.day, r.set)),
+        );
+    }
+    if wrong.is_empty().not() {
+        eprintln!("\nWrong or failed: {}", wrong.join(", "));
+        return ExitCode::FAILURE;
+    }
+    println!("\nNo set got a wrong answer");
+    ExitCode::SUCCESS
 }
 
-fn main() {
-    day!(day1);
-    day!(day2);
-    day!(day3);
-    day!(day4);
-    day!(day5);
-    day!(day6);
-    // day!(day7);
-    day!(day8);
+fn print_header(run: &DayRun, source: &InputSource) {
+    println!("===== Day {}: {} ({}) =====", run.day, run.title, source);
 }
 
-fn read_file(file_name: &str) -> String {
-    std::fs::read_to_string(file_name).unwrap()
+fn print_day(run: &DayRun, verifications: &[Option<Verification>]) {
+    match &run.error {
+        Some(e) if run.has_no_input() => println!("No input: {}", e.reason),
+        Some(e) => println!("Error: {}", e),
+        None => {}
+    }
+    for (part, verification) in run.parts.iter().zip(verifications) {
+        let time = format_duration(part.timing.median);
+        let answer = match &part.answer {
+            Ok(answer) => answer,
+            Err(e) => {
+                println!("Part {}: error: {} ({})", part.part, e, time);
+                continue;
+            }
+        };
+        let status = verification
+            .as_ref()
+            .map(|v| format!(" [{}]", v))
+            .unwrap_or_default();
+        if answer.is_block() {
+            println!("Part {} ({}){}:\n{}", part.part, time, status, answer);
+        } else {
+            println!("Part {}: {} ({}){}", part.part, answer, time, status);
+        }
+    }
 }